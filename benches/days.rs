@@ -0,0 +1,24 @@
+use aoc19::days;
+use aoc19::solution::Part;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// Benchmarks every registered day on its personal input in .inputs/, falling back to the
+// last example registered in answers/ for each part.
+fn bench_days(c: &mut Criterion) {
+    for day in days::all() {
+        let mut group = c.benchmark_group(format!("day{:02}", day.day));
+        for (name, part) in [("part1", Part::One), ("part2", Part::Two)] {
+            let Some((path, input)) = day.timing_input(part) else {
+                eprintln!("day {:02} {name}: skipped, no input", day.day);
+                continue;
+            };
+            eprintln!("day {:02} {name}: {path}", day.day);
+            let solver = day.solver(part);
+            group.bench_function(name, |b| b.iter(|| solver(&input)));
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
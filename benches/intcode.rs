@@ -0,0 +1,57 @@
+use aoc19::intcode::{self, Computer};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Compares its input against 8 and jumps around a fair bit, so it touches
+// every day 5 opcode in position and immediate mode.
+const PROGRAM: &str = include_str!("../answers/examples/day05-3.txt");
+
+// A self test in the style of BOOST, which checks relative mode, large values
+// and memory beyond the end of the program.
+const SELF_TEST: &str = include_str!("../answers/examples/day09-3.txt");
+
+// Whole runs of a single instruction of each length and a halt, which time setting up a
+// computer as much as decoding and executing.
+fn bench_single(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intcode/single");
+    for (name, program) in [
+        ("terminate", "99"),
+        ("two", "104,1,99"),
+        ("three", "1105,0,0,99"),
+        ("four", "1102,2,3,0,99"),
+    ] {
+        let code = intcode::parse_code(program).unwrap();
+        group.bench_function(name, |b| {
+            b.iter(|| Computer::new(black_box(&code), vec![]).run().is_ok())
+        });
+    }
+    group.finish();
+}
+
+fn bench_execute(c: &mut Criterion) {
+    let code = intcode::parse_code(PROGRAM).unwrap();
+    let mut group = c.benchmark_group("intcode/execute");
+    group.bench_function("parse", |b| {
+        b.iter(|| intcode::parse_code(black_box(PROGRAM)))
+    });
+    for input in [7, 8, 9] {
+        group.bench_function(format!("run/{input}"), |b| {
+            b.iter(|| {
+                let mut computer = Computer::new(&code, vec![input]);
+                computer.run().unwrap();
+                computer.take_output()
+            })
+        });
+    }
+    let self_test = intcode::parse_code(SELF_TEST).unwrap();
+    group.bench_function("self-test", |b| {
+        b.iter(|| {
+            let mut computer = Computer::new(&self_test, vec![1]);
+            computer.run().unwrap();
+            computer.take_output()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_single, bench_execute);
+criterion_main!(benches);
@@ -0,0 +1,92 @@
+use aoc19::days;
+use aoc19::error;
+use aoc19::solution::{Day, Part};
+use std::process::exit;
+use std::time::Instant;
+
+const USAGE: &str = "Usage: aoc run <day> <part> [--input FILE]\n       aoc run --all";
+
+fn default_input(day: u32) -> String {
+    format!(".inputs/day{day:02}.txt")
+}
+
+// Prints the answer with its timing and reports whether the solution succeeded.
+fn run(day: &Day, part: Part, path: &str) -> bool {
+    let input = match error::read_file(path) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("day {:02} part {part:?}: {e}", day.day);
+            return false;
+        }
+    };
+    let start = Instant::now();
+    let answer = (day.solver(part))(&input);
+    let elapsed = start.elapsed();
+    match answer {
+        Ok(answer) if answer.contains('\n') => {
+            println!(
+                "day {:02} part {part:?} ({elapsed:.2?}):\n{answer}",
+                day.day
+            );
+            true
+        }
+        Ok(answer) => {
+            println!("day {:02} part {part:?} ({elapsed:.2?}): {answer}", day.day);
+            true
+        }
+        Err(e) => {
+            eprintln!("day {:02} part {part:?} ({elapsed:.2?}): {e}", day.day);
+            false
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    exit(1)
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>();
+    let all = days::all();
+    match args.as_slice() {
+        ["run", "--all"] => {
+            let mut failed = false;
+            for day in &all {
+                let path = default_input(day.day);
+                if !std::path::Path::new(&path).exists() {
+                    println!("day {:02}: skipped, no {path}", day.day);
+                    continue;
+                }
+                for part in [Part::One, Part::Two] {
+                    failed |= !run(day, part, &path);
+                }
+            }
+            if failed {
+                exit(1);
+            }
+        }
+        ["run", day, part, rest @ ..] => {
+            let day = day
+                .parse::<u32>()
+                .ok()
+                .and_then(|day| all.iter().find(|d| d.day == day))
+                .unwrap_or_else(|| usage());
+            let part = match *part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => usage(),
+            };
+            let path = match rest {
+                [] => default_input(day.day),
+                ["--input", path] => String::from(*path),
+                _ => usage(),
+            };
+            if !run(day, part, &path) {
+                exit(1);
+            }
+        }
+        _ => usage(),
+    }
+}
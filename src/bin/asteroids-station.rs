@@ -0,0 +1,67 @@
+use aoc19::asteroids::{Asteroid, Map};
+use aoc19::error::{Error, Result};
+use std::io::{read_to_string, stdin};
+
+const USAGE: &str = "Usage: asteroids-station [--station <x>,<y>] [--order] < map";
+
+fn parse_station(s: &str) -> Option<Asteroid> {
+    let (x, y) = s.split_once(',')?;
+    Some(Asteroid(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+// Reports the station and how many asteroids it detects, then optionally the order in which
+// the laser vaporises the rest.
+fn run(station: Option<Asteroid>, order: bool) -> Result<()> {
+    let map = Map::parse(&read_to_string(stdin())?)?;
+    let station = match station {
+        Some(station) if map.asteroids().contains(&station) => station,
+        Some(Asteroid(x, y)) => {
+            return Err(Error::Solve(format!("there is no asteroid at {x},{y}")));
+        }
+        None => {
+            map.best_station()
+                .ok_or_else(|| Error::Solve(String::from("there are no asteroids")))?
+                .0
+        }
+    };
+    println!(
+        "station at {},{} detects {} asteroids",
+        station.0,
+        station.1,
+        map.visible_from(station)
+    );
+    if order {
+        for (i, a) in map.vaporisation_order(station).iter().enumerate() {
+            println!("{:>4}: {},{}", i + 1, a.0, a.1);
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let mut station = None;
+    let mut order = false;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--order" => order = true,
+            "--station" => match it.next().and_then(|s| parse_station(s)) {
+                Some(s) => station = Some(s),
+                None => {
+                    eprintln!("{USAGE}");
+                    std::process::exit(1);
+                }
+            },
+            _ => {
+                eprintln!("{USAGE}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Err(e) = run(station, order) {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
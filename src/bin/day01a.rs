@@ -1,9 +1,6 @@
-fn main() {
-    let mut total = 0_i64;
-    for line in std::io::stdin().lines() {
-        let weight = line.unwrap().parse::<i32>().unwrap();
-        total += (weight as f64 / 3.0).floor() as i64 - 2
-    }
+use aoc19::days::Day01;
+use aoc19::solution::{self, Part};
 
-    println!("{total}");
+fn main() {
+    solution::main::<Day01>(Part::One)
 }
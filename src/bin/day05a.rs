@@ -1,13 +1,6 @@
-use aoc19::intcode;
+use aoc19::days::Day05;
+use aoc19::solution::{self, Part};
 
 fn main() {
-    let mut line = String::new();
-    _ = std::io::stdin().read_line(&mut line);
-    let code = intcode::parse_code(&line).unwrap();
-    let mut computer = intcode::Computer::new(&code, vec![1]);
-    computer.run().unwrap();
-    for i in computer.output() {
-        println!("{i}");
-    }
+    solution::main::<Day05>(Part::One)
 }
-
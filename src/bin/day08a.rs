@@ -1,21 +1,6 @@
-fn count_occurences(cs: &[char], c: char) -> usize {
-    cs.iter().filter(|&other_c| *other_c == c).count()
-}
+use aoc19::days::Day08;
+use aoc19::solution::{self, Part};
 
 fn main() {
-    let width : usize = 25;
-    let height : usize = 6;
-    let mut line = String::new();
-    _ = std::io::stdin().read_line(&mut line);
-
-    let chars = line.trim().chars().collect::<Vec<char>>();
-
-    let min_chunk = chars.chunks(width * height)
-                         .min_by_key(|&chunk| count_occurences(chunk, '0'))
-                         .unwrap();
-
-    let result = count_occurences(min_chunk, '1')
-        * count_occurences(min_chunk, '2');
-
-    println!("{result}");
-}
\ No newline at end of file
+    solution::main::<Day08>(Part::One)
+}
@@ -0,0 +1,6 @@
+use aoc19::days::Day09;
+use aoc19::solution::{self, Part};
+
+fn main() {
+    solution::main::<Day09>(Part::Two)
+}
@@ -0,0 +1,6 @@
+use aoc19::days::Day10;
+use aoc19::solution::{self, Part};
+
+fn main() {
+    solution::main::<Day10>(Part::Two)
+}
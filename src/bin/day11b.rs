@@ -0,0 +1,6 @@
+use aoc19::days::Day11;
+use aoc19::solution::{self, Part};
+
+fn main() {
+    solution::main::<Day11>(Part::Two)
+}
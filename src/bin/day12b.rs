@@ -0,0 +1,6 @@
+use aoc19::days::Day12;
+use aoc19::solution::{self, Part};
+
+fn main() {
+    solution::main::<Day12>(Part::Two)
+}
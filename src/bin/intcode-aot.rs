@@ -0,0 +1,22 @@
+use aoc19::error::Result;
+use aoc19::intcode::{self, aot};
+
+fn run() -> Result<()> {
+    let mut line = String::new();
+    std::io::stdin().read_line(&mut line)?;
+    let code = intcode::parse_code(&line)?;
+    let program = aot::Program::analyse(&code);
+    if !program.is_static() {
+        eprintln!("Program may modify its own code, guarding compiled instructions");
+    }
+    eprintln!("Compiled {} instructions", program.compiled_count());
+    print!("{}", program.emit());
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,61 @@
+use aoc19::error::{self, Error, Result};
+use aoc19::intcode::ascii::{AsciiComputer, Output};
+use aoc19::intcode::{self, transcript, HaltReason};
+use std::io::Write;
+
+const USAGE: &str = "Usage: intcode-ascii <program> [--record <transcript>]";
+
+// Feeds stdin to the program line by line until it halts or stdin ends.
+fn session(computer: &mut AsciiComputer) -> Result<()> {
+    let mut lines = std::io::stdin().lines();
+    loop {
+        let (halt_reason, output) = computer.run()?;
+        for o in output {
+            match o {
+                Output::Text(text) => print!("{text}"),
+                Output::Value(value) => println!("{value}"),
+            }
+        }
+        _ = std::io::stdout().flush();
+
+        if halt_reason == HaltReason::Terminate {
+            return Ok(());
+        }
+
+        match lines.next() {
+            Some(line) => computer.push_line(&line?),
+            None => return Ok(()),
+        }
+    }
+}
+
+fn run(path: &str, record_path: Option<&str>) -> Result<()> {
+    let code = intcode::parse_code(&error::read_file(path)?).map_err(|e| e.in_file(path))?;
+    let mut computer = AsciiComputer::new(&code);
+    // The transcript is written even if the session fails, as those are the ones to replay.
+    let result = session(&mut computer);
+    if let Some(record_path) = record_path {
+        std::fs::write(
+            record_path,
+            transcript::format_transcript(computer.transcript()),
+        )
+        .map_err(|e| Error::from(e).in_file(record_path))?;
+    }
+    result
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let (path, record_path) = match args.as_slice() {
+        [path] => (path, None),
+        [path, flag, record_path] if flag == "--record" => (path, Some(record_path.as_str())),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = run(path, record_path) {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
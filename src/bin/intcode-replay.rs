@@ -0,0 +1,36 @@
+use aoc19::error::{self, Result};
+use aoc19::intcode::{self, transcript};
+
+// Reports whether the replayed outputs match the transcript.
+fn run(program_path: &str, transcript_path: &str) -> Result<bool> {
+    let code = intcode::parse_code(&error::read_file(program_path)?)
+        .map_err(|e| e.in_file(program_path))?;
+    let events = transcript::parse_transcript(&error::read_file(transcript_path)?)
+        .map_err(|e| e.in_file(transcript_path))?;
+    match transcript::replay(&code, &events)? {
+        None => {
+            println!("Replayed {} events, outputs match", events.len());
+            Ok(true)
+        }
+        Some(divergence) => {
+            println!("Diverged at {divergence}");
+            Ok(false)
+        }
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let [program_path, transcript_path] = args.as_slice() else {
+        eprintln!("Usage: intcode-replay <program> <transcript>");
+        std::process::exit(1);
+    };
+    match run(program_path, transcript_path) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use aoc19::error;
+use aoc19::intcode::ascii::{self, Output};
+use aoc19::intcode::{self, HaltReason};
+use std::process::exit;
+
+const USAGE: &str = "Usage: intcode <program> [--input 1,5] [--set 1=12]... [--steps N] \
+                     [--format lines|csv|ascii]";
+
+const EXIT_HALT: i32 = 0;
+const EXIT_USAGE: i32 = 1;
+const EXIT_INPUT: i32 = 2;
+const EXIT_FAULT: i32 = 3;
+const EXIT_STEPS: i32 = 4;
+
+enum Format {
+    Lines,
+    Csv,
+    Ascii,
+}
+
+struct Options {
+    path: String,
+    input: Vec<i64>,
+    patches: Vec<(usize, i64)>,
+    max_steps: Option<usize>,
+    format: Format,
+}
+
+fn parse_patch(s: &str) -> Result<(usize, i64), String> {
+    let malformed = || format!("--set expects ADDRESS=VALUE, found {s:?}");
+    let (addr, value) = s.split_once('=').ok_or_else(malformed)?;
+    let addr = addr.trim().parse::<usize>().map_err(|_| malformed())?;
+    let value = value.trim().parse::<i64>().map_err(|_| malformed())?;
+    Ok((addr, value))
+}
+
+// Describes the first bad argument in the error.
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut path = None;
+    let mut options = Options {
+        path: String::new(),
+        input: vec![],
+        patches: vec![],
+        max_steps: None,
+        format: Format::Lines,
+    };
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or_else(|| format!("{arg} expects a value"));
+        match arg.as_str() {
+            "--input" => {
+                let values = value()?;
+                options.input = intcode::parse_code(values)
+                    .map_err(|_| format!("--input expects numbers like 1,5, found {values:?}"))?;
+            }
+            "--set" => options.patches.push(parse_patch(value()?)?),
+            "--steps" => {
+                let steps = value()?;
+                options.max_steps = Some(
+                    steps
+                        .parse::<usize>()
+                        .map_err(|_| format!("--steps expects a count, found {steps:?}"))?,
+                )
+            }
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "lines" => Format::Lines,
+                    "csv" => Format::Csv,
+                    "ascii" => Format::Ascii,
+                    format => return Err(format!("unknown format {format:?}")),
+                }
+            }
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg.clone()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg}")),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+    options.path = path.ok_or("missing program")?;
+    Ok(options)
+}
+
+fn print_output(output: &[i64], format: &Format) {
+    match format {
+        Format::Lines => {
+            for i in output {
+                println!("{i}");
+            }
+        }
+        Format::Csv => println!(
+            "{}",
+            output
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        ),
+        Format::Ascii => {
+            for o in ascii::decode_output(output) {
+                match o {
+                    Output::Text(text) => print!("{text}"),
+                    Output::Value(value) => println!("{value}"),
+                }
+            }
+        }
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let options = parse_args(&args).unwrap_or_else(|e| {
+        eprintln!("error: {e}\n{USAGE}");
+        exit(EXIT_USAGE)
+    });
+
+    let mut code = error::read_file(&options.path)
+        .and_then(|source| intcode::parse_code(&source).map_err(|e| e.in_file(&options.path)))
+        .unwrap_or_else(|e| {
+            eprintln!("error: {e}");
+            exit(EXIT_USAGE)
+        });
+    for &(addr, value) in &options.patches {
+        match code.get_mut(addr) {
+            Some(cell) => *cell = value,
+            None => {
+                eprintln!("Address {addr} is outside of the program");
+                exit(EXIT_USAGE)
+            }
+        }
+    }
+
+    let mut computer = intcode::Computer::new(&code, options.input);
+    let result = match options.max_steps {
+        Some(max_steps) => computer.run_limited(max_steps),
+        None => computer.run_adv().map(Some),
+    };
+    print_output(computer.output(), &options.format);
+
+    exit(match result {
+        Ok(Some(HaltReason::Terminate)) => EXIT_HALT,
+        Ok(Some(HaltReason::Input)) => {
+            eprintln!("Program is waiting for input");
+            EXIT_INPUT
+        }
+        Ok(None) => {
+            eprintln!("Step limit reached");
+            EXIT_STEPS
+        }
+        Err(_) => {
+            eprintln!("Program faulted");
+            EXIT_FAULT
+        }
+    })
+}
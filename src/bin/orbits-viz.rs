@@ -0,0 +1,45 @@
+use aoc19::error::Result;
+use aoc19::orbits::{self, viz, OrbitMap};
+use std::io::{read_to_string, stdin};
+
+const USAGE: &str = "Usage: orbits-viz [--dot [--highlight <from> <to>]] [--root <name>] < map";
+
+fn run(dot: bool, highlight: Option<(&str, &str)>, root: &str) -> Result<()> {
+    let m = OrbitMap::parse_with_root(&read_to_string(stdin())?, root)?;
+    if dot {
+        print!("{}", viz::to_dot(&m, highlight)?);
+    } else {
+        print!("{}", viz::to_tree(&m));
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let mut dot = false;
+    let mut highlight = None;
+    let mut root = orbits::ROOT;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match (arg.as_str(), it.clone().next(), it.clone().nth(1)) {
+            ("--dot", _, _) => dot = true,
+            ("--highlight", Some(from), Some(to)) => {
+                highlight = Some((from.as_str(), to.as_str()));
+                it.nth(1);
+            }
+            ("--root", Some(name), _) => {
+                root = name.as_str();
+                it.next();
+            }
+            _ => {
+                eprintln!("{USAGE}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Err(e) = run(dot, highlight, root) {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,45 @@
+use aoc19::error::Result;
+use aoc19::password::{self, Rules};
+
+const USAGE: &str = "Usage: password-check [--part 1|2] <number|lbound-ubound>...";
+
+fn check(arg: &str, rules: &Rules) -> Result<()> {
+    if arg.contains('-') {
+        let range = password::parse_range(arg, false)?;
+        for number in password::candidates(range, rules) {
+            println!("{number}");
+        }
+    } else {
+        let number = arg.parse::<u64>()?;
+        let failed = password::explain(number, rules);
+        if failed.is_empty() {
+            println!("{number}: valid");
+        } else {
+            println!("{number}: fails {}", failed.join(", "));
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+    let rules = match args.next_if(|arg| arg == "--part") {
+        Some(_) => match args.next().as_deref() {
+            Some("1") => Rules::part1(),
+            Some("2") => Rules::part2(),
+            _ => {
+                eprintln!("{USAGE}");
+                std::process::exit(1);
+            }
+        },
+        None => Rules::part2(),
+    };
+
+    for arg in args {
+        if let Err(e) = check(&arg, &rules) {
+            eprintln!("error: {arg:?}: {e}");
+            eprintln!("{USAGE}");
+            std::process::exit(1);
+        }
+    }
+}
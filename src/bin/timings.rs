@@ -0,0 +1,79 @@
+use aoc19::days;
+use aoc19::solution::{Day, Part};
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "Usage: timings [--runs N]";
+
+// Runs the solver the given number of times and returns the fastest and the
+// median run, or the error message if the solution fails.
+fn measure(
+    day: &Day,
+    part: Part,
+    input: &str,
+    runs: usize,
+) -> Result<(Duration, Duration), String> {
+    let solver = day.solver(part);
+    let mut times = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        solver(input).map_err(|e| e.to_string())?;
+        times.push(start.elapsed());
+    }
+    times.sort();
+    Ok((times[0], times[times.len() / 2]))
+}
+
+// Times a part on its personal input, or on its last example marked with a star.
+fn cell(day: &Day, part: Part, runs: usize) -> String {
+    let Some((path, input)) = day.timing_input(part) else {
+        return format!("{:>21}", "no input");
+    };
+    let mark = if path.starts_with(".inputs/") {
+        " "
+    } else {
+        "*"
+    };
+    match measure(day, part, &input, runs) {
+        Ok((min, median)) => format!(
+            "{:>10} {:>9}{mark}",
+            format!("{min:.2?}"),
+            format!("{median:.2?}")
+        ),
+        Err(e) => format!("{:>21}", format!("error: {e}")),
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let runs = match args.as_slice() {
+        [] => 10,
+        [flag, n] if flag == "--runs" => match n.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                eprintln!("{USAGE}");
+                exit(1)
+            }
+        },
+        _ => {
+            eprintln!("{USAGE}");
+            exit(1)
+        }
+    };
+
+    println!("{:<5} {:>21} {:>21}", "", "part 1", "part 2");
+    println!(
+        "{:<5} {:>10} {:>10} {:>10} {:>10}",
+        "day", "min", "median", "min", "median"
+    );
+    let mut total = Duration::ZERO;
+    for day in days::all() {
+        let start = Instant::now();
+        let part1 = cell(&day, Part::One, runs);
+        let part2 = cell(&day, Part::Two, runs);
+        total += start.elapsed();
+        println!("{:<5} {part1} {part2}", format!("{:02}", day.day));
+    }
+    println!("total {total:.2?} over {runs} runs per part");
+    println!("* timed on an example from answers/, no personal input in .inputs/");
+}
@@ -0,0 +1,23 @@
+use aoc19::error::{Error, Result};
+use aoc19::wires::{svg, Wire};
+
+fn run() -> Result<()> {
+    let mut wires = Vec::<Wire>::new();
+    for (i, line) in std::io::stdin().lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        wires.push(line.parse::<Wire>().map_err(|e: Error| e.at_line(i + 1))?);
+    }
+
+    print!("{}", svg::render(&wires.iter().collect::<Vec<&Wire>>()));
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
@@ -1,21 +1,25 @@
 pub mod intcode {
+    use crate::error::Error;
     use std::collections::VecDeque;
 
     pub enum ParameterMode {
         Position,
         Immediate,
+        Relative,
     }
 
     pub enum Operation {
         Add {
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         },
         Multiply {
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         },
-        Input,
+        Input(ParameterMode),
         Output(ParameterMode),
         JumpIfTrue {
             fst_mode: ParameterMode,
@@ -28,132 +32,119 @@ pub mod intcode {
         LessThan {
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         },
         Equals {
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         },
+        AdjustRelativeBase(ParameterMode),
         Terminate,
     }
 
     pub struct ParseOperationError;
 
     impl ParameterMode {
-        fn from_char(c: char) -> Result<ParameterMode, ParseOperationError> {
-            c.to_string()
-                .parse::<i32>()
-                .map_err(|_| ParseOperationError)
-                .and_then(|i| match i {
-                    0 => Ok(ParameterMode::Position),
-                    1 => Ok(ParameterMode::Immediate),
-                    _ => Err(ParseOperationError),
-                })
-        }
-    }
-
-    impl Operation {
-        fn from_single_digit_integer(integer: i32) -> Result<Operation, ParseOperationError> {
-            match integer {
-                1 => Ok(Operation::Add {
-                    fst_mode: ParameterMode::Position,
-                    snd_mode: ParameterMode::Position,
-                }),
-                2 => Ok(Operation::Multiply {
-                    fst_mode: ParameterMode::Position,
-                    snd_mode: ParameterMode::Position,
-                }),
-                3 => Ok(Operation::Input),
-                4 => Ok(Operation::Output(ParameterMode::Position)),
-                5 => Ok(Operation::JumpIfTrue {
-                    fst_mode: ParameterMode::Position,
-                    snd_mode: ParameterMode::Position,
-                }),
-                6 => Ok(Operation::JumpIfFalse {
-                    fst_mode: ParameterMode::Position,
-                    snd_mode: ParameterMode::Position,
-                }),
-                7 => Ok(Operation::LessThan {
-                    fst_mode: ParameterMode::Position,
-                    snd_mode: ParameterMode::Position,
-                }),
-                8 => Ok(Operation::Equals {
-                    fst_mode: ParameterMode::Position,
-                    snd_mode: ParameterMode::Position,
-                }),
+        fn from_digit(digit: i64) -> Result<ParameterMode, ParseOperationError> {
+            match digit {
+                0 => Ok(ParameterMode::Position),
+                1 => Ok(ParameterMode::Immediate),
+                2 => Ok(ParameterMode::Relative),
                 _ => Err(ParseOperationError),
             }
         }
 
-        fn from_three_digit_integer(integer: i32) -> Result<Operation, ParseOperationError> {
-            match integer {
-                101 => Ok(Operation::Add {
-                    fst_mode: ParameterMode::Immediate,
-                    snd_mode: ParameterMode::Position,
-                }),
-                102 => Ok(Operation::Multiply {
-                    fst_mode: ParameterMode::Immediate,
-                    snd_mode: ParameterMode::Position,
-                }),
-                104 => Ok(Operation::Output(ParameterMode::Immediate)),
-                105 => Ok(Operation::JumpIfTrue {
-                    fst_mode: ParameterMode::Immediate,
-                    snd_mode: ParameterMode::Position,
-                }),
-                106 => Ok(Operation::JumpIfFalse {
-                    fst_mode: ParameterMode::Immediate,
-                    snd_mode: ParameterMode::Position,
-                }),
-                107 => Ok(Operation::LessThan {
-                    fst_mode: ParameterMode::Immediate,
-                    snd_mode: ParameterMode::Position,
-                }),
-                108 => Ok(Operation::Equals {
-                    fst_mode: ParameterMode::Immediate,
-                    snd_mode: ParameterMode::Position,
-                }),
-                _ => Err(ParseOperationError),
+        // Parameters that are written to cannot be in immediate mode.
+        fn from_dest_digit(digit: i64) -> Result<ParameterMode, ParseOperationError> {
+            match ParameterMode::from_digit(digit)? {
+                ParameterMode::Immediate => Err(ParseOperationError),
+                mode => Ok(mode),
             }
         }
+    }
 
-        fn from_four_digit_integer(digits: &[char]) -> Result<Operation, ParseOperationError> {
-            format!("{}{}", digits[2], digits[3])
-                .parse::<i32>()
-                .map_err(|_| ParseOperationError)
-                .and_then(|i| ParameterMode::from_char(digits[1]).map(|mode| (i, mode)))
-                .and_then(|(i, fst_mode)| {
-                    ParameterMode::from_char(digits[0]).map(|mode| (i, fst_mode, mode))
-                })
-                .and_then(|(i, fst_mode, snd_mode)| match i {
-                    1 => Ok(Operation::Add { fst_mode, snd_mode }),
-                    2 => Ok(Operation::Multiply { fst_mode, snd_mode }),
-                    5 => Ok(Operation::JumpIfTrue { fst_mode, snd_mode }),
-                    6 => Ok(Operation::JumpIfFalse { fst_mode, snd_mode }),
-                    7 => Ok(Operation::LessThan { fst_mode, snd_mode }),
-                    8 => Ok(Operation::Equals { fst_mode, snd_mode }),
-                    _ => Err(ParseOperationError),
-                })
-        }
-
-        fn from_integer(integer: i32) -> Result<Operation, ParseOperationError> {
-            let digits: Vec<char> = integer.to_string().chars().collect();
-            match digits.len() {
-                1 => Operation::from_single_digit_integer(integer),
-                2 => {
-                    if integer != 99 {
-                        Err(ParseOperationError)
-                    } else {
-                        Ok(Operation::Terminate)
-                    }
-                }
-                3 => Operation::from_three_digit_integer(integer),
-                4 => Operation::from_four_digit_integer(&digits),
-                _ => Err(ParseOperationError),
+    impl Operation {
+        pub(crate) fn from_integer(integer: i64) -> Result<Operation, ParseOperationError> {
+            if integer < 0 {
+                return Err(ParseOperationError);
             }
+            let modes = integer / 100;
+            let digit = |n: u32| modes / 10_i64.pow(n) % 10;
+            let mode = |n: u32| ParameterMode::from_digit(digit(n));
+            let dst_mode = |n: u32| ParameterMode::from_dest_digit(digit(n));
+            let (op, params) = match integer % 100 {
+                1 => (
+                    Operation::Add {
+                        fst_mode: mode(0)?,
+                        snd_mode: mode(1)?,
+                        dst_mode: dst_mode(2)?,
+                    },
+                    3,
+                ),
+                2 => (
+                    Operation::Multiply {
+                        fst_mode: mode(0)?,
+                        snd_mode: mode(1)?,
+                        dst_mode: dst_mode(2)?,
+                    },
+                    3,
+                ),
+                3 => (Operation::Input(dst_mode(0)?), 1),
+                4 => (Operation::Output(mode(0)?), 1),
+                5 => (
+                    Operation::JumpIfTrue {
+                        fst_mode: mode(0)?,
+                        snd_mode: mode(1)?,
+                    },
+                    2,
+                ),
+                6 => (
+                    Operation::JumpIfFalse {
+                        fst_mode: mode(0)?,
+                        snd_mode: mode(1)?,
+                    },
+                    2,
+                ),
+                7 => (
+                    Operation::LessThan {
+                        fst_mode: mode(0)?,
+                        snd_mode: mode(1)?,
+                        dst_mode: dst_mode(2)?,
+                    },
+                    3,
+                ),
+                8 => (
+                    Operation::Equals {
+                        fst_mode: mode(0)?,
+                        snd_mode: mode(1)?,
+                        dst_mode: dst_mode(2)?,
+                    },
+                    3,
+                ),
+                9 => (Operation::AdjustRelativeBase(mode(0)?), 1),
+                99 => (Operation::Terminate, 0),
+                _ => return Err(ParseOperationError),
+            };
+            // Modes for parameters the operation does not have are rejected.
+            if modes / 10_i64.pow(params) != 0 {
+                return Err(ParseOperationError);
+            }
+            Ok(op)
         }
     }
 
-    pub fn parse_code(str: &str) -> Result<Vec<i32>, std::num::ParseIntError> {
-        return str.trim().split(',').map(|x| x.parse::<i32>()).collect();
+    // Reports the 1-based column of the first value that is not a number.
+    pub fn parse_code(str: &str) -> Result<Vec<i64>, Error> {
+        let mut column = 1 + str.len() - str.trim_start().len();
+        str.trim()
+            .split(',')
+            .map(|x| {
+                let at = column;
+                column += x.len() + 1;
+                x.parse::<i64>().map_err(|e| Error::from(e).at_column(at))
+            })
+            .collect()
     }
 
     #[derive(PartialEq, Eq)]
@@ -166,20 +157,59 @@ pub mod intcode {
     #[derive(Debug)]
     pub struct ExecError;
 
+    // Writes at or above this address fault instead of growing memory without bound.
+    pub const MEMORY_LIMIT: usize = 1 << 24;
+
+    // Memory past the end of the program reads as zero and grows when written to, up to
+    // MEMORY_LIMIT; negative addresses fault.
     pub struct Computer {
-        memory: Vec<i32>,
+        memory: Vec<i64>,
         ptr: usize,
-        input: VecDeque<i32>,
-        output: Vec<i32>,
+        relative_base: i64,
+        input: VecDeque<i64>,
+        output: Vec<i64>,
     }
 
     impl Computer {
-        pub fn new(code: &[i32], input: Vec<i32>) -> Computer {
+        pub fn new(code: &[i64], input: Vec<i64>) -> Computer {
             Computer {
-                memory: Vec::<i32>::from(code),
+                memory: Vec::<i64>::from(code),
                 ptr: 0,
-                input: VecDeque::<i32>::from(input),
-                output: Vec::<i32>::new(),
+                relative_base: 0,
+                input: VecDeque::<i64>::from(input),
+                output: Vec::<i64>::new(),
+            }
+        }
+
+        fn read(&self, addr: i64) -> Result<i64, ExecError> {
+            let addr = usize::try_from(addr).map_err(|_| ExecError)?;
+            Ok(self.memory.get(addr).copied().unwrap_or(0))
+        }
+
+        fn write(&mut self, addr: i64, value: i64) -> Result<(), ExecError> {
+            let addr = usize::try_from(addr)
+                .ok()
+                .filter(|&addr| addr < MEMORY_LIMIT)
+                .ok_or(ExecError)?;
+            if addr >= self.memory.len() {
+                self.memory.resize(addr + 1, 0);
+            }
+            self.memory[addr] = value;
+            Ok(())
+        }
+
+        fn address(&self, offset: usize, mode: ParameterMode) -> Result<i64, ExecError> {
+            let value = self.memory.get(self.ptr + offset).copied().unwrap_or(0);
+            match mode {
+                ParameterMode::Relative => self.relative_base.checked_add(value).ok_or(ExecError),
+                _ => Ok(value),
+            }
+        }
+
+        fn param(&self, offset: usize, mode: ParameterMode) -> Result<i64, ExecError> {
+            match mode {
+                ParameterMode::Immediate => self.address(offset, mode),
+                _ => self.read(self.address(offset, mode)?),
             }
         }
 
@@ -187,19 +217,12 @@ pub mod intcode {
             &mut self,
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         ) -> Result<(), ExecError> {
-            let left_operand = match fst_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 1] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 1],
-            };
-
-            let right_operand = match snd_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 2] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 2],
-            };
-
-            let dest = self.memory[self.ptr + 3] as usize;
-            self.memory[dest] = left_operand + right_operand;
+            let left_operand = self.param(1, fst_mode)?;
+            let right_operand = self.param(2, snd_mode)?;
+            let value = left_operand.checked_add(right_operand).ok_or(ExecError)?;
+            self.write(self.address(3, dst_mode)?, value)?;
             self.ptr += 4;
             Ok(())
         }
@@ -208,19 +231,12 @@ pub mod intcode {
             &mut self,
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         ) -> Result<(), ExecError> {
-            let left_operand = match fst_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 1] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 1],
-            };
-
-            let right_operand = match snd_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 2] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 2],
-            };
-
-            let dest = self.memory[self.ptr + 3] as usize;
-            self.memory[dest] = left_operand * right_operand;
+            let left_operand = self.param(1, fst_mode)?;
+            let right_operand = self.param(2, snd_mode)?;
+            let value = left_operand.checked_mul(right_operand).ok_or(ExecError)?;
+            self.write(self.address(3, dst_mode)?, value)?;
             self.ptr += 4;
             Ok(())
         }
@@ -230,18 +246,11 @@ pub mod intcode {
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
         ) -> Result<(), ExecError> {
-            let value = match fst_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 1] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 1],
-            };
+            let value = self.param(1, fst_mode)?;
 
             if value != 0 {
-                let destination = match snd_mode {
-                    ParameterMode::Position => self.memory[self.memory[self.ptr + 2] as usize],
-                    ParameterMode::Immediate => self.memory[self.ptr + 2],
-                };
-
-                self.ptr = destination as usize;
+                let destination = self.param(2, snd_mode)?;
+                self.ptr = usize::try_from(destination).map_err(|_| ExecError)?;
                 Ok(())
             } else {
                 self.ptr += 3;
@@ -254,18 +263,11 @@ pub mod intcode {
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
         ) -> Result<(), ExecError> {
-            let value = match fst_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 1] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 1],
-            };
+            let value = self.param(1, fst_mode)?;
 
             if value == 0 {
-                let destination = match snd_mode {
-                    ParameterMode::Position => self.memory[self.memory[self.ptr + 2] as usize],
-                    ParameterMode::Immediate => self.memory[self.ptr + 2],
-                };
-
-                self.ptr = destination as usize;
+                let destination = self.param(2, snd_mode)?;
+                self.ptr = usize::try_from(destination).map_err(|_| ExecError)?;
                 Ok(())
             } else {
                 self.ptr += 3;
@@ -277,19 +279,12 @@ pub mod intcode {
             &mut self,
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         ) -> Result<(), ExecError> {
-            let left_operand = match fst_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 1] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 1],
-            };
-
-            let right_operand = match snd_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 2] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 2],
-            };
-
-            let dest = self.memory[self.ptr + 3] as usize;
-            self.memory[dest] = if left_operand < right_operand { 1 } else { 0 };
+            let left_operand = self.param(1, fst_mode)?;
+            let right_operand = self.param(2, snd_mode)?;
+            let value = if left_operand < right_operand { 1 } else { 0 };
+            self.write(self.address(3, dst_mode)?, value)?;
             self.ptr += 4;
             Ok(())
         }
@@ -298,98 +293,3474 @@ pub mod intcode {
             &mut self,
             fst_mode: ParameterMode,
             snd_mode: ParameterMode,
+            dst_mode: ParameterMode,
         ) -> Result<(), ExecError> {
-            let left_operand = match fst_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 1] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 1],
-            };
-
-            let right_operand = match snd_mode {
-                ParameterMode::Position => self.memory[self.memory[self.ptr + 2] as usize],
-                ParameterMode::Immediate => self.memory[self.ptr + 2],
-            };
-
-            let dest = self.memory[self.ptr + 3] as usize;
-            self.memory[dest] = if left_operand == right_operand { 1 } else { 0 };
+            let left_operand = self.param(1, fst_mode)?;
+            let right_operand = self.param(2, snd_mode)?;
+            let value = if left_operand == right_operand { 1 } else { 0 };
+            self.write(self.address(3, dst_mode)?, value)?;
             self.ptr += 4;
             Ok(())
         }
 
         fn exec_op(&mut self, op: Operation) -> Result<(), ExecError> {
             match op {
-                Operation::Add { fst_mode, snd_mode } => self.exec_add(fst_mode, snd_mode),
-                Operation::Multiply { fst_mode, snd_mode } => {
-                    self.exec_multiply(fst_mode, snd_mode)
-                }
-                Operation::Input => match self.input.pop_front() {
+                Operation::Add {
+                    fst_mode,
+                    snd_mode,
+                    dst_mode,
+                } => self.exec_add(fst_mode, snd_mode, dst_mode),
+                Operation::Multiply {
+                    fst_mode,
+                    snd_mode,
+                    dst_mode,
+                } => self.exec_multiply(fst_mode, snd_mode, dst_mode),
+                Operation::Input(mode) => match self.input.pop_front() {
                     None => Err(ExecError),
                     Some(i) => {
-                        let dest = self.memory[self.ptr + 1];
-                        self.memory[dest as usize] = i;
-                        self.ptr += 2;
-                        Ok(())
-                    }
-                },
-                Operation::Output(mode) => match mode {
-                    ParameterMode::Position => {
-                        self.output
-                            .push(self.memory[self.memory[self.ptr + 1] as usize]);
-                        self.ptr += 2;
-                        Ok(())
-                    }
-                    ParameterMode::Immediate => {
-                        self.output.push(self.memory[self.ptr + 1]);
+                        self.write(self.address(1, mode)?, i)?;
                         self.ptr += 2;
                         Ok(())
                     }
                 },
+                Operation::Output(mode) => {
+                    let value = self.param(1, mode)?;
+                    self.output.push(value);
+                    self.ptr += 2;
+                    Ok(())
+                }
                 Operation::JumpIfTrue { fst_mode, snd_mode } => {
                     self.exec_jump_if_true(fst_mode, snd_mode)
                 }
                 Operation::JumpIfFalse { fst_mode, snd_mode } => {
                     self.exec_jump_if_false(fst_mode, snd_mode)
                 }
-                Operation::LessThan { fst_mode, snd_mode } => {
-                    self.exec_less_than(fst_mode, snd_mode)
-                }
-                Operation::Equals { fst_mode, snd_mode } => {
-                    self.exec_equals(fst_mode, snd_mode)
+                Operation::LessThan {
+                    fst_mode,
+                    snd_mode,
+                    dst_mode,
+                } => self.exec_less_than(fst_mode, snd_mode, dst_mode),
+                Operation::Equals {
+                    fst_mode,
+                    snd_mode,
+                    dst_mode,
+                } => self.exec_equals(fst_mode, snd_mode, dst_mode),
+                Operation::AdjustRelativeBase(mode) => {
+                    let offset = self.param(1, mode)?;
+                    self.relative_base = self.relative_base.checked_add(offset).ok_or(ExecError)?;
+                    self.ptr += 2;
+                    Ok(())
                 }
                 Operation::Terminate => Err(ExecError),
             }
         }
 
-        pub fn run(&mut self) -> Result<(), ExecError> {
-            Operation::from_integer(self.memory[self.ptr])
+        fn step(&mut self, stop_on_input: bool) -> Result<Option<HaltReason>, ExecError> {
+            Operation::from_integer(self.memory.get(self.ptr).copied().unwrap_or(0))
                 .map_err(|_| ExecError {})
                 .and_then(|op| match op {
-                    Operation::Terminate => Ok(()),
-                    _ => self.exec_op(op).and_then(|_| self.run()),
+                    Operation::Terminate => Ok(Some(HaltReason::Terminate)),
+                    Operation::Input(_) if stop_on_input && self.input.is_empty() => {
+                        Ok(Some(HaltReason::Input))
+                    }
+                    _ => self.exec_op(op).map(|_| None),
                 })
         }
 
+        pub fn run(&mut self) -> Result<(), ExecError> {
+            while self.step(false)?.is_none() {}
+            Ok(())
+        }
+
         pub fn run_adv(&mut self) -> Result<HaltReason, ExecError> {
-            Operation::from_integer(self.memory[self.ptr])
-                .map_err(|_| ExecError {})
-                .and_then(|op| match op {
-                    Operation::Terminate => Ok(HaltReason::Terminate),
-                    Operation::Input if self.input.is_empty() => Ok(HaltReason::Input),
-                    _ => self.exec_op(op).and_then(|_| self.run_adv()),
-                })
+            loop {
+                if let Some(halt_reason) = self.step(true)? {
+                    return Ok(halt_reason);
+                }
+            }
+        }
+
+        pub fn run_limited(&mut self, max_steps: usize) -> Result<Option<HaltReason>, ExecError> {
+            for _ in 0..max_steps {
+                if let Some(halt_reason) = self.step(true)? {
+                    return Ok(Some(halt_reason));
+                }
+            }
+            Ok(None)
         }
 
-        pub fn push_input(&mut self, i: i32)
+        pub fn push_input(&mut self, i: i64)
         {
             self.input.push_back(i)
         }
 
-        pub fn output(&self) -> &[i32] {
+        pub fn output(&self) -> &[i64] {
             &self.output
         }
 
-        pub fn pop_output(&mut self) -> Option<i32>
+        pub fn memory(&self) -> &[i64] {
+            &self.memory
+        }
+
+        pub fn pop_output(&mut self) -> Option<i64>
         {
             self.output.pop()
         }
+
+        pub fn take_output(&mut self) -> Vec<i64> {
+            std::mem::take(&mut self.output)
+        }
+    }
+
+    pub enum SelfTest {
+        Passed(i64),
+        Malfunctions(Vec<i64>),
+        NoOutput,
+    }
+
+    // Runs a self-checking program such as BOOST in test mode. A working computer makes it
+    // output just a keycode, otherwise it outputs every opcode that malfunctioned. A program
+    // that halts without output is not a self test at all.
+    pub fn self_test(code: &[i64]) -> Result<SelfTest, ExecError> {
+        let mut computer = Computer::new(code, vec![1]);
+        computer.run()?;
+        match computer.take_output().as_slice() {
+            [] => Ok(SelfTest::NoOutput),
+            [keycode] => Ok(SelfTest::Passed(*keycode)),
+            opcodes => Ok(SelfTest::Malfunctions(Vec::<i64>::from(opcodes))),
+        }
+    }
+
+    pub mod ascii {
+        use super::transcript::{Event, Recorder};
+        use super::{ExecError, HaltReason};
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub enum Output {
+            Text(String),
+            Value(i64),
+        }
+
+        pub fn decode_output(values: &[i64]) -> Vec<Output> {
+            let mut decoded = Vec::<Output>::new();
+            for &value in values {
+                match (u8::try_from(value), decoded.last_mut()) {
+                    (Ok(c), Some(Output::Text(text))) if c.is_ascii() => text.push(c as char),
+                    (Ok(c), _) if c.is_ascii() => {
+                        decoded.push(Output::Text(String::from(c as char)))
+                    }
+                    _ => decoded.push(Output::Value(value)),
+                }
+            }
+            decoded
+        }
+
+        pub fn encode_line(line: &str) -> Vec<i64> {
+            line.chars().chain("\n".chars()).map(|c| c as i64).collect()
+        }
+
+        pub struct AsciiComputer {
+            recorder: Recorder,
+        }
+
+        impl AsciiComputer {
+            pub fn new(code: &[i64]) -> AsciiComputer {
+                AsciiComputer {
+                    recorder: Recorder::new(code),
+                }
+            }
+
+            pub fn push_line(&mut self, line: &str) {
+                for i in encode_line(line) {
+                    self.recorder.push_input(i);
+                }
+            }
+
+            pub fn run(&mut self) -> Result<(HaltReason, Vec<Output>), ExecError> {
+                let halt_reason = self.recorder.run_adv()?;
+                Ok((halt_reason, decode_output(&self.recorder.take_output())))
+            }
+
+            pub fn transcript(&self) -> &[Event] {
+                self.recorder.events()
+            }
+        }
+    }
+
+    pub mod transcript {
+        use super::{Computer, ExecError, HaltReason};
+        use crate::error::Error;
+        use std::collections::VecDeque;
+        use std::fmt;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Event {
+            Input(i64),
+            Output(i64),
+        }
+
+        impl fmt::Display for Event {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    Event::Input(i) => write!(f, "in {i}"),
+                    Event::Output(i) => write!(f, "out {i}"),
+                }
+            }
+        }
+
+        fn parse_event(line: &str) -> Result<Event, Error> {
+            let malformed = || Error::syntax("\"in N\" or \"out N\"", line);
+            let mut it = line.split_whitespace();
+            let kind = it.next().ok_or_else(malformed)?;
+            let value = it
+                .next()
+                .ok_or_else(malformed)
+                .and_then(|s| s.parse::<i64>().map_err(|_| malformed()))?;
+            if it.next().is_some() {
+                return Err(malformed());
+            }
+            match kind {
+                "in" => Ok(Event::Input(value)),
+                "out" => Ok(Event::Output(value)),
+                _ => Err(malformed()),
+            }
+        }
+
+        pub fn parse_transcript(s: &str) -> Result<Vec<Event>, Error> {
+            s.lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| parse_event(line).map_err(|e| e.at_line(i + 1)))
+                .collect()
+        }
+
+        pub fn format_transcript(events: &[Event]) -> String {
+            events.iter().map(|e| format!("{e}\n")).collect()
+        }
+
+        pub struct Recorder {
+            computer: Computer,
+            events: Vec<Event>,
+            recorded: usize,
+        }
+
+        impl Recorder {
+            pub fn new(code: &[i64]) -> Recorder {
+                Recorder {
+                    computer: Computer::new(code, vec![]),
+                    events: Vec::<Event>::new(),
+                    recorded: 0,
+                }
+            }
+
+            pub fn push_input(&mut self, i: i64) {
+                self.events.push(Event::Input(i));
+                self.computer.push_input(i)
+            }
+
+            // Output from before a fault is recorded too.
+            pub fn run_adv(&mut self) -> Result<HaltReason, ExecError> {
+                let halt_reason = self.computer.run_adv();
+                let output = &self.computer.output()[self.recorded..];
+                self.events.extend(output.iter().map(|&i| Event::Output(i)));
+                self.recorded += output.len();
+                halt_reason
+            }
+
+            pub fn output(&self) -> &[i64] {
+                self.computer.output()
+            }
+
+            pub fn take_output(&mut self) -> Vec<i64> {
+                self.recorded = 0;
+                self.computer.take_output()
+            }
+
+            pub fn events(&self) -> &[Event] {
+                &self.events
+            }
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct Divergence {
+            pub index: usize,
+            pub expected: Option<Event>,
+            pub actual: Option<Event>,
+        }
+
+        impl fmt::Display for Divergence {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let describe = |e: &Option<Event>| match e {
+                    Some(e) => e.to_string(),
+                    None => String::from("nothing"),
+                };
+                write!(
+                    f,
+                    "event {}: expected {}, got {}",
+                    self.index + 1,
+                    describe(&self.expected),
+                    describe(&self.actual)
+                )
+            }
+        }
+
+        pub fn replay(code: &[i64], events: &[Event]) -> Result<Option<Divergence>, ExecError> {
+            let mut computer = Computer::new(code, vec![]);
+            let mut pending = VecDeque::<i64>::new();
+            let mut halt_reason = HaltReason::Input;
+            for (index, &expected) in events.iter().enumerate() {
+                match expected {
+                    Event::Input(i) => computer.push_input(i),
+                    Event::Output(_) => {
+                        if pending.is_empty() && halt_reason == HaltReason::Input {
+                            halt_reason = computer.run_adv()?;
+                            pending.extend(computer.take_output());
+                        }
+                        let actual = pending.pop_front().map(Event::Output);
+                        if actual != Some(expected) {
+                            return Ok(Some(Divergence {
+                                index,
+                                expected: Some(expected),
+                                actual,
+                            }));
+                        }
+                    }
+                }
+            }
+
+            if halt_reason == HaltReason::Input {
+                computer.run_adv()?;
+                pending.extend(computer.take_output());
+            }
+            Ok(pending.pop_front().map(|i| Divergence {
+                index: events.len(),
+                expected: None,
+                actual: Some(Event::Output(i)),
+            }))
+        }
+    }
+
+    pub mod aot {
+        use super::{Operation, ParameterMode};
+        use std::collections::{BTreeMap, HashSet};
+        use std::fmt::Write;
+
+        fn length(op: &Operation) -> usize {
+            match op {
+                Operation::Add { .. }
+                | Operation::Multiply { .. }
+                | Operation::LessThan { .. }
+                | Operation::Equals { .. } => 4,
+                Operation::JumpIfTrue { .. } | Operation::JumpIfFalse { .. } => 3,
+                Operation::Input(_) | Operation::Output(_) | Operation::AdjustRelativeBase(_) => 2,
+                Operation::Terminate => 1,
+            }
+        }
+
+        fn dest(op: &Operation) -> Option<(usize, &ParameterMode)> {
+            match op {
+                Operation::Add { dst_mode, .. }
+                | Operation::Multiply { dst_mode, .. }
+                | Operation::LessThan { dst_mode, .. }
+                | Operation::Equals { dst_mode, .. } => Some((3, dst_mode)),
+                Operation::Input(dst_mode) => Some((1, dst_mode)),
+                _ => None,
+            }
+        }
+
+        // The opcode, destination and jump target, which are always compiled in as constants.
+        fn fixed_offsets(op: &Operation) -> Vec<usize> {
+            let mut offsets = vec![0];
+            offsets.extend(dest(op).map(|(offset, _)| offset));
+            if let Operation::JumpIfTrue { .. } | Operation::JumpIfFalse { .. } = op {
+                offsets.push(2);
+            }
+            offsets
+        }
+
+        pub struct Program {
+            code: Vec<i64>,
+            instructions: BTreeMap<usize, Operation>,
+            written: HashSet<usize>,
+            is_static: bool,
+        }
+
+        impl Program {
+            pub fn analyse(code: &[i64]) -> Program {
+                let mut instructions = BTreeMap::<usize, Operation>::new();
+                let mut jump_cells = Vec::<usize>::new();
+                // Reachable addresses that hold no instruction fault when run, unless they are
+                // written first.
+                let mut invalid = Vec::<usize>::new();
+                // Cleared when control may reach code the analysis cannot see, such as a jump
+                // target in relative mode.
+                let mut complete = true;
+                let mut pending = vec![0_usize];
+                while let Some(addr) = pending.pop() {
+                    if instructions.contains_key(&addr) {
+                        continue;
+                    }
+                    let op = match code.get(addr).map(|&i| Operation::from_integer(i)) {
+                        Some(Ok(op)) => op,
+                        _ => {
+                            invalid.push(addr);
+                            continue;
+                        }
+                    };
+                    if addr + length(&op) > code.len() {
+                        complete = false;
+                        continue;
+                    }
+                    match &op {
+                        Operation::Terminate => {}
+                        Operation::JumpIfTrue { snd_mode, .. }
+                        | Operation::JumpIfFalse { snd_mode, .. } => {
+                            pending.push(addr + 3);
+                            let target = match snd_mode {
+                                ParameterMode::Immediate => Some(code[addr + 2]),
+                                ParameterMode::Position => usize::try_from(code[addr + 2])
+                                    .ok()
+                                    .filter(|&cell| cell < code.len())
+                                    .map(|cell| {
+                                        jump_cells.push(cell);
+                                        code[cell]
+                                    }),
+                                ParameterMode::Relative => None,
+                            };
+                            match target.map(usize::try_from) {
+                                Some(Ok(target)) => pending.push(target),
+                                // Negative targets fault.
+                                Some(Err(_)) => {}
+                                None => complete = false,
+                            }
+                        }
+                        _ => pending.push(addr + length(&op)),
+                    }
+                    instructions.insert(addr, op);
+                }
+
+                let written = instructions
+                    .iter()
+                    .filter_map(|(addr, op)| dest(op).map(|(offset, _)| code[addr + offset]))
+                    .filter_map(|dest| usize::try_from(dest).ok())
+                    .collect::<HashSet<usize>>();
+
+                // Operand cells may be rewritten at run time and are then read from memory. The
+                // program is static when nothing else an instruction was compiled from can
+                // change: no write lands on an opcode, destination or jump target, none is in
+                // relative mode, and control never leaves the analysed code. Otherwise every
+                // compiled instruction checks its cells and defers to the interpreter when they
+                // no longer match the original code.
+                let is_static = complete
+                    && jump_cells
+                        .iter()
+                        .chain(&invalid)
+                        .all(|cell| !written.contains(cell))
+                    && instructions.iter().all(|(addr, op)| {
+                        !matches!(dest(op), Some((_, ParameterMode::Relative)))
+                            && fixed_offsets(op)
+                                .into_iter()
+                                .all(|offset| !written.contains(&(addr + offset)))
+                    });
+
+                Program {
+                    code: Vec::<i64>::from(code),
+                    instructions,
+                    written,
+                    is_static,
+                }
+            }
+
+            pub fn is_static(&self) -> bool {
+                self.is_static
+            }
+
+            pub fn compiled_count(&self) -> usize {
+                self.instructions.len()
+            }
+
+            fn operand(&self, addr: usize, offset: usize, mode: &ParameterMode) -> String {
+                let cell = addr + offset;
+                let value = if self.written.contains(&cell) {
+                    format!("self.memory[{cell}]")
+                } else {
+                    format!("{}", self.code[cell])
+                };
+                match mode {
+                    ParameterMode::Position => format!("self.read({value})?"),
+                    ParameterMode::Immediate => value,
+                    ParameterMode::Relative => format!("self.read(self.relative({value})?)?"),
+                }
+            }
+
+            fn emit_instruction(&self, out: &mut String, addr: usize, op: &Operation) {
+                let next = addr + length(op);
+                let dest = dest(op).map(|(offset, mode)| match mode {
+                    ParameterMode::Relative => {
+                        format!("self.relative({})?", self.code[addr + offset])
+                    }
+                    _ => self.code[addr + offset].to_string(),
+                });
+                if self.is_static {
+                    _ = writeln!(out, "                {addr} => {{");
+                } else {
+                    let fixed = fixed_offsets(op);
+                    let cells = (addr..next)
+                        .filter(|cell| {
+                            fixed.contains(&(cell - addr)) || !self.written.contains(cell)
+                        })
+                        .map(|cell| cell.to_string())
+                        .collect::<Vec<String>>();
+                    _ = writeln!(
+                        out,
+                        "                {addr} if self.unchanged(&[{}]) => {{",
+                        cells.join(", ")
+                    );
+                }
+                match op {
+                    Operation::Add {
+                        fst_mode, snd_mode, ..
+                    }
+                    | Operation::Multiply {
+                        fst_mode, snd_mode, ..
+                    }
+                    | Operation::LessThan {
+                        fst_mode, snd_mode, ..
+                    }
+                    | Operation::Equals {
+                        fst_mode, snd_mode, ..
+                    } => {
+                        let left = self.operand(addr, 1, fst_mode);
+                        let right = self.operand(addr, 2, snd_mode);
+                        let value = match op {
+                            Operation::Add { .. } => "left.checked_add(right).ok_or(ExecError)?",
+                            Operation::Multiply { .. } => {
+                                "left.checked_mul(right).ok_or(ExecError)?"
+                            }
+                            Operation::LessThan { .. } => "(left < right) as i64",
+                            _ => "(left == right) as i64",
+                        };
+                        _ = writeln!(out, "                    let left: i64 = {left};");
+                        _ = writeln!(out, "                    let right: i64 = {right};");
+                        _ = writeln!(
+                            out,
+                            "                    self.write({}, {value})?;",
+                            dest.as_ref().unwrap()
+                        );
+                        _ = writeln!(out, "                    self.ptr = {next};");
+                    }
+                    Operation::Input(_) => {
+                        _ = writeln!(out, "                    match self.input.pop_front() {{");
+                        _ = writeln!(out, "                        Some(i) => {{");
+                        _ = writeln!(
+                            out,
+                            "                            self.write({}, i)?;",
+                            dest.as_ref().unwrap()
+                        );
+                        _ = writeln!(out, "                            self.ptr = {next};");
+                        _ = writeln!(out, "                        }}");
+                        _ = writeln!(out, "                        None if stop_on_input => return Ok(HaltReason::Input),");
+                        _ = writeln!(
+                            out,
+                            "                        None => return Err(ExecError),"
+                        );
+                        _ = writeln!(out, "                    }}");
+                    }
+                    Operation::Output(mode) => {
+                        let value = self.operand(addr, 1, mode);
+                        _ = writeln!(out, "                    self.output.push({value});");
+                        _ = writeln!(out, "                    self.ptr = {next};");
+                    }
+                    Operation::AdjustRelativeBase(mode) => {
+                        let value = self.operand(addr, 1, mode);
+                        _ = writeln!(
+                            out,
+                            "                    self.relative_base = self.relative({value})?;"
+                        );
+                        _ = writeln!(out, "                    self.ptr = {next};");
+                    }
+                    Operation::JumpIfTrue { fst_mode, snd_mode }
+                    | Operation::JumpIfFalse { fst_mode, snd_mode } => {
+                        let value = self.operand(addr, 1, fst_mode);
+                        let target = self.operand(addr, 2, snd_mode);
+                        let cmp = match op {
+                            Operation::JumpIfTrue { .. } => "!=",
+                            _ => "==",
+                        };
+                        _ = writeln!(out, "                    if {value} {cmp} 0 {{");
+                        match target.parse::<usize>() {
+                            Ok(target) => {
+                                _ = writeln!(out, "                        self.ptr = {target};")
+                            }
+                            Err(_) => {
+                                _ = writeln!(
+                                    out,
+                                    "                        self.ptr = usize::try_from({target}).map_err(|_| ExecError)?;"
+                                )
+                            }
+                        }
+                        _ = writeln!(out, "                    }} else {{");
+                        _ = writeln!(out, "                        self.ptr = {next};");
+                        _ = writeln!(out, "                    }}");
+                    }
+                    Operation::Terminate => {
+                        _ = writeln!(out, "                    return Ok(HaltReason::Terminate);");
+                    }
+                }
+                _ = writeln!(out, "                }}");
+            }
+
+            pub fn emit(&self) -> String {
+                let mut out = String::new();
+                let code = self
+                    .code
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<String>>();
+                _ = write!(out, "{}", PRELUDE);
+                _ = writeln!(
+                    out,
+                    "const CODE: [i64; {}] = [{}];",
+                    code.len(),
+                    code.join(", ")
+                );
+                _ = write!(out, "{}", INTERPRETER);
+                if !self.is_static {
+                    _ = write!(out, "{}", GUARD);
+                }
+                _ = writeln!(out, "    fn exec(&mut self, stop_on_input: bool) -> Result<HaltReason, ExecError> {{");
+                _ = writeln!(out, "        loop {{");
+                _ = writeln!(out, "            match self.ptr {{");
+                for (addr, op) in &self.instructions {
+                    self.emit_instruction(&mut out, *addr, op);
+                }
+                _ = writeln!(out, "                _ => {{");
+                _ = writeln!(
+                    out,
+                    "                    if let Some(reason) = self.step(stop_on_input)? {{"
+                );
+                _ = writeln!(out, "                        return Ok(reason);");
+                _ = writeln!(out, "                    }}");
+                _ = writeln!(out, "                }}");
+                _ = writeln!(out, "            }}");
+                _ = writeln!(out, "        }}");
+                _ = writeln!(out, "    }}");
+                _ = writeln!(out, "}}");
+                out
+            }
+        }
+
+        const PRELUDE: &str = "// Generated by intcode-aot.
+use std::collections::VecDeque;
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum HaltReason {
+    Terminate,
+    Input,
+}
+
+#[derive(Debug)]
+pub struct ExecError;
+
+const MEMORY_LIMIT: usize = 1 << 24;
+
+";
+
+        // Compiled instructions of a program that may modify its code only run while the
+        // cells they were compiled from still hold their original values.
+        const GUARD: &str = "    fn unchanged(&self, cells: &[usize]) -> bool {
+        cells.iter().all(|&cell| self.memory[cell] == CODE[cell])
+    }
+
+";
+
+        const INTERPRETER: &str = "
+// Returns the opcode and the modes of its parameters, which must not be immediate for writes.
+fn decode(integer: i64) -> Option<(i64, [i64; 3])> {
+    if integer < 0 {
+        return None;
+    }
+    let opcode = integer % 100;
+    let (params, write) = match opcode {
+        1 | 2 | 7 | 8 => (3, Some(2)),
+        3 => (1, Some(0)),
+        4 | 9 => (1, None),
+        5 | 6 => (2, None),
+        99 => (0, None),
+        _ => return None,
+    };
+    let mut modes = [0; 3];
+    let mut rest = integer / 100;
+    for mode in modes.iter_mut().take(params) {
+        *mode = rest % 10;
+        rest /= 10;
+    }
+    if rest != 0 || modes.iter().any(|&mode| mode > 2) || write.map_or(false, |w| modes[w] == 1) {
+        return None;
+    }
+    Some((opcode, modes))
+}
+
+pub struct Computer {
+    memory: Vec<i64>,
+    ptr: usize,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    output: Vec<i64>,
+}
+
+impl Computer {
+    pub fn new(input: Vec<i64>) -> Computer {
+        Computer {
+            memory: Vec::<i64>::from(CODE),
+            ptr: 0,
+            relative_base: 0,
+            input: VecDeque::<i64>::from(input),
+            output: Vec::<i64>::new(),
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), ExecError> {
+        self.exec(false).map(|_| ())
+    }
+
+    pub fn run_adv(&mut self) -> Result<HaltReason, ExecError> {
+        self.exec(true)
+    }
+
+    pub fn push_input(&mut self, i: i64) {
+        self.input.push_back(i)
+    }
+
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    pub fn pop_output(&mut self) -> Option<i64> {
+        self.output.pop()
+    }
+
+    fn read(&self, addr: i64) -> Result<i64, ExecError> {
+        let addr = usize::try_from(addr).map_err(|_| ExecError)?;
+        Ok(self.memory.get(addr).copied().unwrap_or(0))
+    }
+
+    fn write(&mut self, addr: i64, value: i64) -> Result<(), ExecError> {
+        let addr = usize::try_from(addr)
+            .ok()
+            .filter(|&addr| addr < MEMORY_LIMIT)
+            .ok_or(ExecError)?;
+        if addr >= self.memory.len() {
+            self.memory.resize(addr + 1, 0);
+        }
+        self.memory[addr] = value;
+        Ok(())
+    }
+
+    fn relative(&self, offset: i64) -> Result<i64, ExecError> {
+        self.relative_base.checked_add(offset).ok_or(ExecError)
+    }
+
+    fn address(&self, offset: usize, mode: i64) -> Result<i64, ExecError> {
+        let value = self.memory.get(self.ptr + offset).copied().unwrap_or(0);
+        if mode == 2 {
+            self.relative(value)
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn param(&self, offset: usize, mode: i64) -> Result<i64, ExecError> {
+        let addr = self.address(offset, mode)?;
+        if mode == 1 {
+            Ok(addr)
+        } else {
+            self.read(addr)
+        }
+    }
+
+    fn step(&mut self, stop_on_input: bool) -> Result<Option<HaltReason>, ExecError> {
+        let (opcode, modes) =
+            decode(self.memory.get(self.ptr).copied().unwrap_or(0)).ok_or(ExecError)?;
+        match opcode {
+            1 | 2 | 7 | 8 => {
+                let left = self.param(1, modes[0])?;
+                let right = self.param(2, modes[1])?;
+                let value = match opcode {
+                    1 => left.checked_add(right).ok_or(ExecError)?,
+                    2 => left.checked_mul(right).ok_or(ExecError)?,
+                    7 => (left < right) as i64,
+                    _ => (left == right) as i64,
+                };
+                self.write(self.address(3, modes[2])?, value)?;
+                self.ptr += 4;
+            }
+            3 => match self.input.pop_front() {
+                Some(i) => {
+                    self.write(self.address(1, modes[0])?, i)?;
+                    self.ptr += 2;
+                }
+                None if stop_on_input => return Ok(Some(HaltReason::Input)),
+                None => return Err(ExecError),
+            },
+            4 => {
+                let value = self.param(1, modes[0])?;
+                self.output.push(value);
+                self.ptr += 2;
+            }
+            5 | 6 => {
+                let value = self.param(1, modes[0])?;
+                if (value != 0) == (opcode == 5) {
+                    self.ptr = usize::try_from(self.param(2, modes[1])?).map_err(|_| ExecError)?;
+                } else {
+                    self.ptr += 3;
+                }
+            }
+            9 => {
+                let offset = self.param(1, modes[0])?;
+                self.relative_base = self.relative(offset)?;
+                self.ptr += 2;
+            }
+            _ => return Ok(Some(HaltReason::Terminate)),
+        }
+        Ok(None)
+    }
+
+";
+    }
+}
+
+pub mod wires {
+    use crate::error::Error;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+    use std::str::FromStr;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Direction {
+        Up,
+        Down,
+        Left,
+        Right,
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    pub struct Motion {
+        pub direction: Direction,
+        pub dist: i32,
+    }
+
+    pub fn parse_motion(s: &str) -> Result<Motion, Error> {
+        let malformed = || Error::syntax("a direction U, D, L or R and a distance", s);
+        let mut chars = s.chars();
+        let direction = match chars.next() {
+            Some('U') => Direction::Up,
+            Some('D') => Direction::Down,
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            _ => return Err(malformed()),
+        };
+        chars
+            .as_str()
+            .parse::<i32>()
+            .ok()
+            .filter(|dist| *dist >= 0)
+            .ok_or_else(malformed)
+            .map(|dist| Motion { direction, dist })
+    }
+
+    // Reports the 1-based column of the first malformed motion.
+    pub fn parse_motions(s: &str) -> Result<Vec<Motion>, Error> {
+        let mut column = 1 + s.len() - s.trim_start().len();
+        s.trim()
+            .split(',')
+            .map(|m| {
+                let at = column + m.len() - m.trim_start().len();
+                column += m.len() + 1;
+                parse_motion(m.trim()).map_err(|e| e.at_column(at))
+            })
+            .collect()
+    }
+
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Point(pub i32, pub i32);
+
+    impl Point {
+        pub fn manhattan(&self) -> i32 {
+            self.0.abs() + self.1.abs()
+        }
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    pub struct Segment {
+        pub start: Point,
+        pub direction: Direction,
+        pub length: i32,
+        pub steps: i64,
+    }
+
+    impl Segment {
+        pub fn end(&self) -> Point {
+            let Point(x, y) = self.start;
+            match self.direction {
+                Direction::Up => Point(x, y + self.length),
+                Direction::Down => Point(x, y - self.length),
+                Direction::Left => Point(x - self.length, y),
+                Direction::Right => Point(x + self.length, y),
+            }
+        }
+
+        pub fn is_horizontal(&self) -> bool {
+            matches!(self.direction, Direction::Left | Direction::Right)
+        }
+
+        pub fn min_corner(&self) -> Point {
+            let end = self.end();
+            Point(self.start.0.min(end.0), self.start.1.min(end.1))
+        }
+
+        pub fn max_corner(&self) -> Point {
+            let end = self.end();
+            Point(self.start.0.max(end.0), self.start.1.max(end.1))
+        }
+
+        pub fn steps_to(&self, p: Point) -> i64 {
+            self.steps + ((p.0 - self.start.0).abs() + (p.1 - self.start.1).abs()) as i64
+        }
+    }
+
+    pub struct Wire {
+        segments: Vec<Segment>,
+    }
+
+    impl Wire {
+        pub fn new(motions: &[Motion]) -> Wire {
+            let mut segments = Vec::<Segment>::new();
+            let mut start = Point(0, 0);
+            let mut steps = 0_i64;
+            for &Motion { direction, dist } in motions {
+                let segment = Segment {
+                    start,
+                    direction,
+                    length: dist,
+                    steps,
+                };
+                start = segment.end();
+                steps += dist as i64;
+                segments.push(segment);
+            }
+
+            Wire { segments }
+        }
+
+        pub fn segments(&self) -> &[Segment] {
+            &self.segments
+        }
+
+        pub fn first_arrival(&self, p: Point) -> Option<i64> {
+            self.segments
+                .iter()
+                .filter(|s| {
+                    let (min, max) = (s.min_corner(), s.max_corner());
+                    (min.0..=max.0).contains(&p.0) && (min.1..=max.1).contains(&p.1)
+                })
+                .map(|s| s.steps_to(p))
+                .min()
+        }
+    }
+
+    impl FromStr for Wire {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Wire, Error> {
+            parse_motions(s).map(|motions| Wire::new(&motions))
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Intersection {
+        pub point: Point,
+        pub distance: i32,
+        pub steps: i64,
+        pub arrivals: Vec<(usize, i64)>,
+    }
+
+    // A stretch along which the same wires run together, from its lower to its upper end.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Overlap {
+        pub from: Point,
+        pub to: Point,
+        pub wires: Vec<usize>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Crossings {
+        // Single points where the wires meet outside the overlaps, or where more of them meet
+        // than run along the overlap, by distance and then steps.
+        pub points: Vec<Intersection>,
+        pub overlaps: Vec<Overlap>,
+        // The meetings nearest the origin and with the fewest combined steps, searched over
+        // the points and all along the overlaps.
+        pub closest: Option<Intersection>,
+        pub fewest_steps: Option<Intersection>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Revisit {
+        pub point: Point,
+        pub visits: Vec<i64>,
+    }
+
+    // A stretch a wire runs along `times` times, from its lower to its upper end.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Retrace {
+        pub from: Point,
+        pub to: Point,
+        pub times: usize,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct SelfCrossings {
+        pub points: Vec<Revisit>,
+        pub retraces: Vec<Retrace>,
+    }
+
+    fn line_key(segment: &Segment) -> (bool, i32) {
+        if segment.is_horizontal() {
+            (true, segment.start.1)
+        } else {
+            (false, segment.start.0)
+        }
+    }
+
+    fn span(segment: &Segment) -> (i32, i32) {
+        let (min, max) = (segment.min_corner(), segment.max_corner());
+        if segment.is_horizontal() {
+            (min.0, max.0)
+        } else {
+            (min.1, max.1)
+        }
+    }
+
+    fn within(from: Point, to: Point, p: Point) -> bool {
+        (from.0..=to.0).contains(&p.0) && (from.1..=to.1).contains(&p.1)
+    }
+
+    // Whether `b` follows `a` along a line.
+    fn adjacent(a: Point, b: Point) -> bool {
+        b.0 - a.0 + b.1 - a.1 == 1
+    }
+
+    type StretchIndex = HashMap<(bool, i32), Vec<(Point, Point, usize)>>;
+
+    // Stretches that do not overlap, given by their ends in order, by the line they lie on.
+    fn index_stretches(ends: impl Iterator<Item = (Point, Point)>) -> StretchIndex {
+        let mut lines = StretchIndex::new();
+        for (k, (from, to)) in ends.enumerate() {
+            let key = if from.1 == to.1 {
+                (true, from.1)
+            } else {
+                (false, from.0)
+            };
+            lines.entry(key).or_default().push((from, to, k));
+        }
+        lines
+    }
+
+    // The stretches through `p`, at most one on each line.
+    fn stretches_at(index: &StretchIndex, p: Point) -> impl Iterator<Item = usize> + '_ {
+        [(true, p.1), (false, p.0)]
+            .into_iter()
+            .filter_map(move |key| {
+                let line = index.get(&key)?;
+                let before = line.partition_point(|&(from, _, _)| from <= p);
+                let &(from, to, k) = line.get(before.checked_sub(1)?)?;
+                within(from, to, p).then_some(k)
+            })
+    }
+
+    // Segments by the line they lie on, each line ordered by span.
+    fn index_lines(segments: &[Segment]) -> HashMap<(bool, i32), Vec<usize>> {
+        let mut lines = HashMap::<(bool, i32), Vec<usize>>::new();
+        for (i, segment) in segments.iter().enumerate() {
+            lines.entry(line_key(segment)).or_default().push(i);
+        }
+        for members in lines.values_mut() {
+            members.sort_unstable_by_key(|&i| span(&segments[i]));
+        }
+        lines
+    }
+
+    // Every segment passing through `p`.
+    fn passing<'a>(
+        segments: &'a [Segment],
+        lines: &'a HashMap<(bool, i32), Vec<usize>>,
+        p: Point,
+    ) -> impl Iterator<Item = usize> + 'a {
+        [((true, p.1), p.0), ((false, p.0), p.1)]
+            .into_iter()
+            .flat_map(move |(key, t)| {
+                let members = lines.get(&key).map_or(&[][..], |m| m.as_slice());
+                let started = members.partition_point(|&i| span(&segments[i]).0 <= t);
+                members[..started]
+                    .iter()
+                    .copied()
+                    .filter(move |&i| span(&segments[i]).1 >= t)
+            })
+    }
+
+    // The points of a stretch from `from` to `to` where the distance or the steps can be smallest:
+    // its ends and the points nearest the origin, including those beside it since the origin
+    // itself never counts. Along a piece covered by the same segments the steps of each wire are
+    // the least of some linear functions, so their sum is smallest at an end.
+    fn landmarks(from: Point, to: Point) -> BTreeSet<Point> {
+        let mut points = BTreeSet::from([from, to]);
+        for t in -1..=1 {
+            points.insert(Point(t.clamp(from.0, to.0), t.clamp(from.1, to.1)));
+        }
+        points
+    }
+
+    type Piece = (Point, Point, BTreeMap<usize, usize>);
+
+    // Splits each line with more than one segment into the pieces covered by the same segments,
+    // in order along the line, with how many segments of each owner cover them. `cover` gives
+    // the span a segment covers on its line, if any. Found by sweeping the ends of the spans.
+    fn pieces<C, O>(lines: &HashMap<(bool, i32), Vec<usize>>, cover: C, owner: O) -> Vec<Vec<Piece>>
+    where
+        C: Fn(usize) -> Option<(i32, i32)>,
+        O: Fn(usize) -> usize,
+    {
+        let mut result = Vec::<Vec<Piece>>::new();
+        for (&(horizontal, c), members) in lines.iter().filter(|(_, m)| m.len() > 1) {
+            let point = |t: i64| {
+                let t = t as i32;
+                if horizontal {
+                    Point(t, c)
+                } else {
+                    Point(c, t)
+                }
+            };
+            let mut events = Vec::<(i64, bool, usize)>::new();
+            for &i in members {
+                if let Some((lo, hi)) = cover(i) {
+                    events.push((lo as i64, true, owner(i)));
+                    events.push((hi as i64 + 1, false, owner(i)));
+                }
+            }
+            events.sort_unstable();
+
+            let mut counts = BTreeMap::<usize, usize>::new();
+            let mut line = Vec::<Piece>::new();
+            for (k, &(t, starts, o)) in events.iter().enumerate() {
+                if starts {
+                    *counts.entry(o).or_default() += 1;
+                } else if let Some(count) = counts.get_mut(&o) {
+                    *count -= 1;
+                    if *count == 0 {
+                        counts.remove(&o);
+                    }
+                }
+                match events.get(k + 1) {
+                    Some(&(next, _, _)) if next > t && !counts.is_empty() => {
+                        line.push((point(t), point(next - 1), counts.clone()))
+                    }
+                    _ => {}
+                }
+            }
+            result.push(line);
+        }
+        result
+    }
+
+    // Joins neighbouring pieces of a line for which `same` holds into stretches.
+    fn join<S>(line: &[Piece], same: S) -> Vec<Piece>
+    where
+        S: Fn(&BTreeMap<usize, usize>, &BTreeMap<usize, usize>) -> bool,
+    {
+        let mut joined = Vec::<Piece>::new();
+        for (from, to, counts) in line {
+            match joined.last_mut() {
+                Some((_, end, last)) if same(last, counts) && adjacent(*end, *from) => *end = *to,
+                _ => joined.push((*from, *to, counts.clone())),
+            }
+        }
+        joined
+    }
+
+    // Reports every point where a vertical and a horizontal segment for which `accept` holds
+    // meet, found by sweeping a vertical line over the plane while keeping the horizontal
+    // segments it cuts ordered by height.
+    fn for_each_crossing<A, R>(segments: &[Segment], accept: A, mut report: R)
+    where
+        A: Fn(usize, usize) -> bool,
+        R: FnMut(Point),
+    {
+        const INSERT: u8 = 0;
+        const QUERY: u8 = 1;
+        const REMOVE: u8 = 2;
+
+        let mut events = Vec::<(i32, u8, usize)>::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let (lo, hi) = (segment.min_corner().0, segment.max_corner().0);
+            if segment.is_horizontal() {
+                events.push((lo, INSERT, i));
+                events.push((hi, REMOVE, i));
+            } else {
+                events.push((lo, QUERY, i));
+            }
+        }
+        events.sort_unstable();
+
+        let mut active = BTreeSet::<(i32, usize)>::new();
+        for (x, kind, i) in events {
+            let y = segments[i].start.1;
+            match kind {
+                INSERT => _ = active.insert((y, i)),
+                QUERY => {
+                    let (lo, hi) = span(&segments[i]);
+                    for &(y, j) in active.range((lo, 0)..=(hi, usize::MAX)) {
+                        if accept(j, i) {
+                            report(Point(x, y));
+                        }
+                    }
+                }
+                _ => _ = active.remove(&(y, i)),
+            }
+        }
+    }
+
+    // Where at least `min_wires` of the wires meet, other than at the origin: the stretches
+    // along which they run together, split where a wire joins or leaves, and the single points
+    // where they cross. A `min_wires` below 2 counts as 2, as a point on a single wire is no
+    // crossing. Each point comes with the first arrival of every wire passing through it.
+    pub fn crossings(wires: &[&Wire], min_wires: usize) -> Crossings {
+        let min_wires = min_wires.max(2);
+        let segments = wires
+            .iter()
+            .map(|w| w.segments())
+            .collect::<Vec<&[Segment]>>()
+            .concat();
+        let owners = wires
+            .iter()
+            .enumerate()
+            .flat_map(|(owner, w)| std::iter::repeat_n(owner, w.segments().len()))
+            .collect::<Vec<usize>>();
+        let lines = index_lines(&segments);
+
+        let meet = |point: Point| {
+            let mut arrivals = BTreeMap::<usize, i64>::new();
+            for k in passing(&segments, &lines, point) {
+                let steps = segments[k].steps_to(point);
+                arrivals
+                    .entry(owners[k])
+                    .and_modify(|first| *first = steps.min(*first))
+                    .or_insert(steps);
+            }
+            (point != Point(0, 0) && arrivals.len() >= min_wires).then(|| Intersection {
+                point,
+                distance: point.manhattan(),
+                steps: arrivals.values().sum(),
+                arrivals: arrivals.into_iter().collect(),
+            })
+        };
+
+        let mut candidates = BTreeSet::<Point>::new();
+        let mut overlaps = Vec::<Overlap>::new();
+        let lines_pieces = pieces(&lines, |i| Some(span(&segments[i])), |i| owners[i]);
+        for line in &lines_pieces {
+            for (from, to, _) in line.iter().filter(|p| p.2.len() >= min_wires) {
+                candidates.extend(landmarks(*from, *to));
+            }
+            let same_wires =
+                |a: &BTreeMap<usize, usize>, b: &BTreeMap<usize, usize>| a.keys().eq(b.keys());
+            for (from, to, counts) in join(line, same_wires) {
+                if counts.len() < min_wires {
+                    continue;
+                }
+                if from == to {
+                    candidates.insert(from);
+                } else {
+                    overlaps.push(Overlap {
+                        from,
+                        to,
+                        wires: counts.into_keys().collect(),
+                    });
+                }
+            }
+        }
+        // A wire crossing an overlap changes the steps at that point alone, so the points
+        // beside it are where the steps along the rest of the overlap can be smallest.
+        for_each_crossing(
+            &segments,
+            |i, j| owners[i] != owners[j],
+            |Point(x, y)| {
+                candidates.extend([
+                    Point(x, y),
+                    Point(x - 1, y),
+                    Point(x + 1, y),
+                    Point(x, y - 1),
+                    Point(x, y + 1),
+                ])
+            },
+        );
+
+        let met = candidates
+            .into_iter()
+            .filter_map(meet)
+            .collect::<Vec<Intersection>>();
+        let closest = met
+            .iter()
+            .min_by_key(|i| (i.distance, i.steps, i.point))
+            .cloned();
+        let fewest_steps = met
+            .iter()
+            .min_by_key(|i| (i.steps, i.distance, i.point))
+            .cloned();
+
+        // Points along an overlap only add to it where more wires meet than run along it.
+        overlaps.sort_by_key(|o| (o.from, o.to));
+        let index = index_stretches(overlaps.iter().map(|o| (o.from, o.to)));
+        let mut points = met
+            .into_iter()
+            .filter(|i| {
+                !stretches_at(&index, i.point)
+                    .any(|k| overlaps[k].wires.iter().eq(i.arrivals.iter().map(|a| &a.0)))
+            })
+            .collect::<Vec<Intersection>>();
+        points.sort_by_key(|i| (i.distance, i.steps, i.point));
+        Crossings {
+            points,
+            overlaps,
+            closest,
+            fewest_steps,
+        }
+    }
+
+    pub fn intersections(fst: &Wire, snd: &Wire) -> Crossings {
+        crossings(&[fst, snd], 2)
+    }
+
+    // Where a wire passes more than once: the stretches it runs along again, split where the
+    // number of times changes, and the single points it crosses, with the step counts of all
+    // visits in ascending order. Consecutive segments share their joint on the same step, which
+    // is not a revisit.
+    pub fn self_crossings(wire: &Wire) -> SelfCrossings {
+        let segments = wire.segments();
+        let lines = index_lines(segments);
+        // Each segment after the first leaves its start to the one before, so that every step
+        // is counted once.
+        let cover = |i: usize| {
+            let (lo, hi) = span(&segments[i]);
+            let (lo, hi) = match segments[i].direction {
+                _ if i == 0 => (lo, hi),
+                Direction::Right | Direction::Up => (lo + 1, hi),
+                _ => (lo, hi - 1),
+            };
+            (lo <= hi).then_some((lo, hi))
+        };
+
+        let mut candidates = BTreeSet::<Point>::new();
+        let mut retraces = Vec::<Retrace>::new();
+        for line in &pieces(&lines, cover, |_| 0) {
+            for (from, to, counts) in line {
+                if counts[&0] > 1 && from == to {
+                    candidates.insert(*from);
+                }
+            }
+            for (from, to, counts) in join(line, |a, b| a == b) {
+                if counts[&0] > 1 && from != to {
+                    retraces.push(Retrace {
+                        from,
+                        to,
+                        times: counts[&0],
+                    });
+                }
+            }
+        }
+        for_each_crossing(segments, |_, _| true, |p| _ = candidates.insert(p));
+
+        retraces.sort_by_key(|t| (t.from, t.to));
+        let index = index_stretches(retraces.iter().map(|t| (t.from, t.to)));
+        let mut points = candidates
+            .into_iter()
+            .filter_map(|point| {
+                let visits = passing(segments, &lines, point)
+                    .map(|k| segments[k].steps_to(point))
+                    .collect::<BTreeSet<i64>>();
+                (visits.len() > 1).then(|| Revisit {
+                    point,
+                    visits: visits.into_iter().collect(),
+                })
+            })
+            .filter(|r| !stretches_at(&index, r.point).any(|k| retraces[k].times == r.visits.len()))
+            .collect::<Vec<Revisit>>();
+        points.sort_by_key(|r| (r.visits[0], r.point));
+        SelfCrossings { points, retraces }
+    }
+
+    pub mod svg {
+        use super::{crossings, Intersection, Point, Wire};
+        use std::fmt::Write;
+
+        const COLOURS: [&str; 6] = [
+            "#1f77b4", "#d62728", "#2ca02c", "#9467bd", "#ff7f0e", "#17becf",
+        ];
+
+        pub fn render(wires: &[&Wire]) -> String {
+            let mut min = Point(0, 0);
+            let mut max = Point(0, 0);
+            for segment in wires.iter().flat_map(|w| w.segments()) {
+                let (lo, hi) = (segment.min_corner(), segment.max_corner());
+                min = Point(min.0.min(lo.0), min.1.min(lo.1));
+                max = Point(max.0.max(hi.0), max.1.max(hi.1));
+            }
+            // Sizes are given in puzzle units, so scale strokes and markers with the layout.
+            let extent = (max.0 - min.0).max(max.1 - min.1).max(1);
+            let unit = extent as f64 / 200.0;
+            let margin = 10.0 * unit;
+
+            let mut out = String::new();
+            _ = writeln!(
+                out,
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+                min.0 as f64 - margin,
+                -max.1 as f64 - margin,
+                (max.0 - min.0) as f64 + 2.0 * margin,
+                (max.1 - min.1) as f64 + 2.0 * margin
+            );
+
+            for (i, wire) in wires.iter().enumerate() {
+                let points = wire
+                    .segments()
+                    .first()
+                    .map(|s| s.start)
+                    .into_iter()
+                    .chain(wire.segments().iter().map(|s| s.end()))
+                    .map(|p| format!("{},{}", p.0, -p.1))
+                    .collect::<Vec<String>>();
+                _ = writeln!(
+                    out,
+                    "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{unit}\"/>",
+                    points.join(" "),
+                    COLOURS[i % COLOURS.len()]
+                );
+            }
+
+            _ = writeln!(
+                out,
+                "  <circle cx=\"0\" cy=\"0\" r=\"{}\" fill=\"black\"><title>origin</title></circle>",
+                4.0 * unit
+            );
+
+            let crossings = crossings(wires, 2);
+            for overlap in &crossings.overlaps {
+                let wires = overlap
+                    .wires
+                    .iter()
+                    .map(|w| w.to_string())
+                    .collect::<Vec<String>>();
+                _ = writeln!(
+                    out,
+                    "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"gold\" \
+                     stroke-opacity=\"0.6\" stroke-width=\"{}\" stroke-linecap=\"round\">\
+                     <title>overlap of wires {}</title></line>",
+                    overlap.from.0,
+                    -overlap.from.1,
+                    overlap.to.0,
+                    -overlap.to.1,
+                    6.0 * unit,
+                    wires.join(", ")
+                );
+            }
+
+            // The closest meeting is highlighted, and marked even if it lies along an overlap.
+            let closest = crossings.closest.as_ref();
+            let mut marked = crossings.points.iter().collect::<Vec<&Intersection>>();
+            if let Some(closest) = closest.filter(|c| !crossings.points.contains(c)) {
+                marked.push(closest);
+            }
+            for i in marked {
+                let label = format!("d={} s={}", i.distance, i.steps);
+                let (radius, colour) = if Some(i) == closest {
+                    (5.0 * unit, "gold")
+                } else {
+                    (3.0 * unit, "black")
+                };
+                _ = writeln!(
+                    out,
+                    "  <circle cx=\"{}\" cy=\"{}\" r=\"{radius}\" fill=\"{colour}\" stroke=\"black\" \
+                     stroke-width=\"{}\"><title>{label}</title></circle>",
+                    i.point.0,
+                    -i.point.1,
+                    unit / 2.0
+                );
+                _ = writeln!(
+                    out,
+                    "  <text x=\"{}\" y=\"{}\" font-size=\"{}\">{label}</text>",
+                    i.point.0 as f64 + radius,
+                    -i.point.1 as f64 - radius,
+                    12.0 * unit
+                );
+            }
+
+            out.push_str("</svg>\n");
+            out
+        }
+    }
+}
+
+pub mod fuel {
+    pub fn module_fuel(mass: u128) -> u128 {
+        (mass / 3).saturating_sub(2)
+    }
+
+    pub fn total_fuel(mass: u128) -> u128 {
+        let mut total = 0;
+        let mut fuel = module_fuel(mass);
+        while fuel > 0 {
+            total += fuel;
+            fuel = module_fuel(fuel);
+        }
+        total
+    }
+
+    pub fn checked_sum<I>(fuel: I) -> Option<u128>
+    where
+        I: IntoIterator<Item = u128>,
+    {
+        fuel.into_iter()
+            .try_fold(0_u128, |total, fuel| total.checked_add(fuel))
+    }
+
+    // Total fuel of every mass below the bound, so that a batch of modules only iterates the
+    // rocket equation until the fuel drops into the table.
+    pub struct FuelTable {
+        totals: Vec<u64>,
+    }
+
+    impl FuelTable {
+        pub fn new(bound: usize) -> FuelTable {
+            let mut totals = Vec::<u64>::with_capacity(bound);
+            for mass in 0..bound {
+                let fuel = module_fuel(mass as u128) as usize;
+                let total = if fuel == 0 {
+                    0
+                } else {
+                    fuel as u64 + totals[fuel]
+                };
+                totals.push(total);
+            }
+            FuelTable { totals }
+        }
+
+        pub fn total_fuel(&self, mass: u128) -> u128 {
+            let mut total = 0;
+            let mut fuel = module_fuel(mass);
+            while fuel > 0 && fuel >= self.totals.len() as u128 {
+                total += fuel;
+                fuel = module_fuel(fuel);
+            }
+            total + fuel + self.totals.get(fuel as usize).copied().unwrap_or(0) as u128
+        }
+
+        pub fn batch_total(&self, masses: &[u128]) -> Option<u128> {
+            checked_sum(masses.iter().map(|&mass| self.total_fuel(mass)))
+        }
+    }
+}
+
+pub mod input {
+    use crate::error::Error;
+    use std::any::type_name;
+    use std::io::BufRead;
+    use std::str::FromStr;
+
+    // Parses every non-blank line, trimmed, reporting the first offending line by its 1-based
+    // number.
+    pub fn read_values<T, R>(reader: R) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        R: BufRead,
+    {
+        let mut values = Vec::<T>::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| Error::from(e).at_line(i + 1))?;
+            let text = line.trim();
+            if text.is_empty() {
+                continue;
+            }
+            values.push(
+                text.parse::<T>()
+                    .map_err(|_| Error::syntax(type_name::<T>(), text).at_line(i + 1))?,
+            );
+        }
+        Ok(values)
+    }
+
+    pub fn parse_values<T: FromStr>(s: &str) -> Result<Vec<T>, Error> {
+        read_values(s.as_bytes())
+    }
+}
+
+pub mod password {
+    use crate::error::Error;
+    use std::collections::{HashMap, HashSet};
+    use std::fmt;
+    use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+    #[derive(Clone, Copy)]
+    pub struct RunRule {
+        pub name: &'static str,
+        pub predicate: fn(u32) -> bool,
+    }
+
+    pub const DOUBLE_DIGITS: RunRule = RunRule {
+        name: "has_double_digits_at_least_once",
+        predicate: |run| run >= 2,
+    };
+
+    pub const EXACT_DOUBLE_DIGITS: RunRule = RunRule {
+        name: "has_exact_double_digits_at_least_once",
+        predicate: |run| run == 2,
+    };
+
+    // Holds for every pair of adjacent digits, the first one given first.
+    #[derive(Clone, Copy)]
+    pub struct OrderRule {
+        pub name: &'static str,
+        pub predicate: fn(u8, u8) -> bool,
+    }
+
+    pub const NONDECREASING: OrderRule = OrderRule {
+        name: "digits_form_nondecreasing_sequence",
+        predicate: |prev, next| prev <= next,
+    };
+
+    impl OrderRule {
+        fn holds(&self, digits: &[u8]) -> bool {
+            digits.windows(2).all(|w| (self.predicate)(w[0], w[1]))
+        }
+
+        // Smallest number not below the given one whose digits follow the rule, found digit by
+        // digit keeping the largest prefix of the number that can still be completed.
+        pub fn next_from(&self, number: u64) -> Option<u64> {
+            let mut digits = digits(number);
+            loop {
+                let mut dead_ends = HashSet::<(usize, u8)>::new();
+                if let Some(found) = self.complete(&digits, 0, None, true, &mut dead_ends) {
+                    return found
+                        .into_iter()
+                        .try_fold(0_u64, |n, d| n.checked_mul(10)?.checked_add(d as u64));
+                }
+                // No number of this length works, so try the smallest one with another digit.
+                if digits.len() >= 20 {
+                    return None;
+                }
+                digits = vec![0; digits.len() + 1];
+                digits[0] = 1;
+            }
+        }
+
+        fn complete(
+            &self,
+            digits: &[u8],
+            pos: usize,
+            last: Option<u8>,
+            tight: bool,
+            dead_ends: &mut HashSet<(usize, u8)>,
+        ) -> Option<Vec<u8>> {
+            if pos == digits.len() {
+                return Some(Vec::new());
+            }
+            let lowest = if tight { digits[pos] } else { 0 };
+            for d in lowest..=9 {
+                if last.is_some_and(|last| !(self.predicate)(last, d)) {
+                    continue;
+                }
+                let tight = tight && d == digits[pos];
+                // Without a bound, whether a suffix exists only depends on where it starts.
+                if !tight && dead_ends.contains(&(pos, d)) {
+                    continue;
+                }
+                if let Some(mut rest) = self.complete(digits, pos + 1, Some(d), tight, dead_ends) {
+                    rest.insert(0, d);
+                    return Some(rest);
+                }
+                if !tight {
+                    dead_ends.insert((pos, d));
+                }
+            }
+            None
+        }
+    }
+
+    // A password is valid when its digits follow the order rule, if any, and every run rule is
+    // satisfied by the length of at least one run of equal digits.
+    pub struct Rules {
+        pub order: Option<OrderRule>,
+        pub runs: Vec<RunRule>,
+    }
+
+    impl Rules {
+        pub fn part1() -> Rules {
+            Rules {
+                order: Some(NONDECREASING),
+                runs: vec![DOUBLE_DIGITS],
+            }
+        }
+
+        pub fn part2() -> Rules {
+            Rules {
+                order: Some(NONDECREASING),
+                runs: vec![EXACT_DOUBLE_DIGITS],
+            }
+        }
+
+        fn satisfied(&self, mask: u32, run: u32) -> u32 {
+            self.runs
+                .iter()
+                .enumerate()
+                .filter(|(_, rule)| (rule.predicate)(run))
+                .fold(mask, |mask, (i, _)| mask | 1 << i)
+        }
+
+        fn allows(&self, last: u8, d: u8) -> bool {
+            self.order.is_none_or(|order| (order.predicate)(last, d))
+        }
+
+        fn all_satisfied(&self) -> u32 {
+            (1 << self.runs.len()) - 1
+        }
+
+        pub fn is_valid(&self, number: u64) -> bool {
+            let digits = digits(number);
+            let mask = runs(&digits)
+                .into_iter()
+                .fold(0, |mask, run| self.satisfied(mask, run));
+            self.order.is_none_or(|order| order.holds(&digits)) && mask == self.all_satisfied()
+        }
+    }
+
+    fn digits(number: u64) -> Vec<u8> {
+        number.to_string().bytes().map(|b| b - b'0').collect()
+    }
+
+    fn runs(digits: &[u8]) -> Vec<u32> {
+        digits
+            .chunk_by(|a, b| a == b)
+            .map(|run| run.len() as u32)
+            .collect()
+    }
+
+    pub fn has_double_digits_at_least_once(number: u64) -> bool {
+        runs(&digits(number))
+            .into_iter()
+            .any(DOUBLE_DIGITS.predicate)
+    }
+
+    pub fn has_exact_double_digits_at_least_once(number: u64) -> bool {
+        runs(&digits(number))
+            .into_iter()
+            .any(EXACT_DOUBLE_DIGITS.predicate)
+    }
+
+    pub fn digits_form_nondecreasing_sequence(number: u64) -> bool {
+        NONDECREASING.holds(&digits(number))
+    }
+
+    // Digit dynamic programming: the count of valid suffixes only depends on how many digits
+    // remain, the last digit, the length of the run it ends and which run rules already hold.
+    struct Counter<'a> {
+        rules: &'a Rules,
+        memo: HashMap<(usize, u8, u32, u32), u64>,
+    }
+
+    impl Counter<'_> {
+        fn next_state(&self, last: u8, run: u32, mask: u32, d: u8) -> (u32, u32) {
+            if d == last {
+                (run + 1, mask)
+            } else {
+                (1, self.rules.satisfied(mask, run))
+            }
+        }
+
+        fn free(&mut self, remaining: usize, last: u8, run: u32, mask: u32) -> u64 {
+            if remaining == 0 {
+                return (self.rules.satisfied(mask, run) == self.rules.all_satisfied()) as u64;
+            }
+            if let Some(&count) = self.memo.get(&(remaining, last, run, mask)) {
+                return count;
+            }
+            let mut count = 0;
+            let rules = self.rules;
+            for d in (0..=9).filter(|&d| rules.allows(last, d)) {
+                let (run, mask) = self.next_state(last, run, mask, d);
+                count += self.free(remaining - 1, d, run, mask);
+            }
+            self.memo.insert((remaining, last, run, mask), count);
+            count
+        }
+
+        fn tight(&mut self, digits: &[u8], pos: usize, last: u8, run: u32, mask: u32) -> u64 {
+            if pos == digits.len() {
+                return (self.rules.satisfied(mask, run) == self.rules.all_satisfied()) as u64;
+            }
+            let mut count = 0;
+            let rules = self.rules;
+            for d in (0..=digits[pos]).filter(|&d| rules.allows(last, d)) {
+                let (run, mask) = self.next_state(last, run, mask, d);
+                count += if d < digits[pos] {
+                    self.free(digits.len() - pos - 1, d, run, mask)
+                } else {
+                    self.tight(digits, pos + 1, d, run, mask)
+                };
+            }
+            count
+        }
+
+        // Valid numbers in 1..=number.
+        fn count_up_to(&mut self, number: u64) -> u64 {
+            if number == 0 {
+                return 0;
+            }
+            let digits = digits(number);
+            let mut count = 0;
+            for len in 1..digits.len() {
+                for first in 1..=9 {
+                    count += self.free(len - 1, first, 1, 0);
+                }
+            }
+            for first in 1..=digits[0] {
+                count += if first < digits[0] {
+                    self.free(digits.len() - 1, first, 1, 0)
+                } else {
+                    self.tight(&digits, 1, first, 1, 0)
+                };
+            }
+            count
+        }
+    }
+
+    fn inclusive_bounds<R: RangeBounds<u64>>(range: &R) -> Option<(u64, u64)> {
+        let lo = match range.start_bound() {
+            Bound::Included(&lo) => Some(lo),
+            Bound::Excluded(&lo) => lo.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let hi = match range.end_bound() {
+            Bound::Included(&hi) => Some(hi),
+            Bound::Excluded(&hi) => hi.checked_sub(1),
+            Bound::Unbounded => Some(u64::MAX),
+        };
+        lo.zip(hi).filter(|(lo, hi)| lo <= hi)
+    }
+
+    pub fn count<R: RangeBounds<u64>>(range: R, rules: &Rules) -> u64 {
+        match inclusive_bounds(&range) {
+            Some((lo, hi)) => {
+                let mut counter = Counter {
+                    rules,
+                    memo: HashMap::new(),
+                };
+                let zero = (lo == 0 && rules.is_valid(0)) as u64;
+                counter.count_up_to(hi) - counter.count_up_to(lo.saturating_sub(1)) + zero
+            }
+            None => 0,
+        }
+    }
+
+    pub struct Candidates<'a> {
+        rules: &'a Rules,
+        next: Option<u64>,
+        hi: u64,
+    }
+
+    impl Iterator for Candidates<'_> {
+        type Item = u64;
+
+        fn next(&mut self) -> Option<u64> {
+            loop {
+                let mut number = self.next?;
+                if let Some(order) = &self.rules.order {
+                    number = order.next_from(number)?;
+                }
+                if number > self.hi {
+                    self.next = None;
+                    return None;
+                }
+                self.next = number.checked_add(1);
+                if self.rules.is_valid(number) {
+                    return Some(number);
+                }
+            }
+        }
+    }
+
+    pub fn candidates<R: RangeBounds<u64>>(range: R, rules: &Rules) -> Candidates<'_> {
+        let (next, hi) = match inclusive_bounds(&range) {
+            Some((lo, hi)) => (Some(lo), hi),
+            None => (None, 0),
+        };
+        Candidates { rules, next, hi }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum ParseRangeError {
+        Malformed,
+        Reversed,
+        NotSixDigits,
+    }
+
+    impl fmt::Display for ParseRangeError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                ParseRangeError::Malformed => write!(f, "expected a range like 108457-562041"),
+                ParseRangeError::Reversed => write!(f, "lower bound exceeds upper bound"),
+                ParseRangeError::NotSixDigits => write!(f, "bounds must have six digits"),
+            }
+        }
+    }
+
+    fn parse_bound(s: &str, strict: bool) -> Result<u64, ParseRangeError> {
+        let s = s.trim();
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseRangeError::Malformed);
+        }
+        let bound = s.parse::<u64>().map_err(|_| ParseRangeError::Malformed)?;
+        if strict && !(100_000..=999_999).contains(&bound) {
+            return Err(ParseRangeError::NotSixDigits);
+        }
+        Ok(bound)
+    }
+
+    // Parses the puzzle's `lbound-ubound`, both bounds included. Strict mode additionally
+    // requires six-digit bounds as the puzzle states.
+    pub fn parse_range(s: &str, strict: bool) -> Result<RangeInclusive<u64>, Error> {
+        let mut spl = s.trim().split('-');
+        let lbound = spl
+            .next()
+            .ok_or(ParseRangeError::Malformed)
+            .and_then(|s| parse_bound(s, strict))?;
+        let ubound = spl
+            .next()
+            .ok_or(ParseRangeError::Malformed)
+            .and_then(|s| parse_bound(s, strict))?;
+        if spl.next().is_some() {
+            return Err(Error::Range(ParseRangeError::Malformed));
+        }
+        if lbound > ubound {
+            return Err(Error::Range(ParseRangeError::Reversed));
+        }
+        Ok(lbound..=ubound)
+    }
+
+    // Names of the rules the number fails, empty for a valid password.
+    pub fn explain(number: u64, rules: &Rules) -> Vec<&'static str> {
+        let runs = runs(&digits(number));
+        let mut failed = Vec::<&'static str>::new();
+        for rule in &rules.runs {
+            if !runs.iter().any(|&run| (rule.predicate)(run)) {
+                failed.push(rule.name);
+            }
+        }
+        if let Some(order) = rules.order.filter(|order| !order.holds(&digits(number))) {
+            failed.push(order.name);
+        }
+        failed
+    }
+}
+
+pub mod orbits {
+    use crate::error::Error;
+    use std::collections::HashMap;
+    use std::fmt;
+
+    pub const ROOT: &str = "COM";
+
+    // Everything wrong with the shape of a map, each list sorted by name.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    pub struct Diagnostic {
+        pub missing_root: Option<String>,
+        pub extra_roots: Vec<String>,
+        pub cycles: Vec<String>,
+        pub unreachable: Vec<String>,
+    }
+
+    impl fmt::Display for Diagnostic {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let mut problems = Vec::<String>::new();
+            if let Some(root) = &self.missing_root {
+                problems.push(format!("root {root} is missing or orbits another body"));
+            }
+            if !self.extra_roots.is_empty() {
+                problems.push(format!("other roots: {}", self.extra_roots.join(", ")));
+            }
+            if !self.cycles.is_empty() {
+                problems.push(format!("bodies in cycles: {}", self.cycles.join(", ")));
+            }
+            if !self.unreachable.is_empty() {
+                problems.push(format!(
+                    "unreachable bodies: {}",
+                    self.unreachable.join(", ")
+                ));
+            }
+            write!(f, "{}", problems.join("; "))
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum OrbitError {
+        Malformed(String),
+        DuplicateChild(String),
+        Invalid(Diagnostic),
+        UnknownBody(String),
+        NotOrbiting(String),
+    }
+
+    impl fmt::Display for OrbitError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                OrbitError::Malformed(line) => write!(f, "malformed orbit {line:?}"),
+                OrbitError::DuplicateChild(name) => write!(f, "{name} orbits more than one body"),
+                OrbitError::Invalid(diagnostic) => write!(f, "invalid orbit map: {diagnostic}"),
+                OrbitError::UnknownBody(name) => write!(f, "unknown body {name}"),
+                OrbitError::NotOrbiting(name) => write!(f, "{name} does not orbit anything"),
+            }
+        }
+    }
+
+    pub fn read_orbit(line: &str) -> Result<(&str, &str), Error> {
+        let mut it = line.trim().split(')');
+        match (it.next(), it.next(), it.next()) {
+            (Some(parent), Some(child), None) if !parent.is_empty() && !child.is_empty() => {
+                Ok((parent, child))
+            }
+            _ => Err(Error::Orbit(OrbitError::Malformed(String::from(line)))),
+        }
+    }
+
+    // Bodies are interned into indices, each one storing the index of the body it orbits.
+    pub struct OrbitMap {
+        names: Vec<String>,
+        ids: HashMap<String, usize>,
+        parents: Vec<Option<usize>>,
+        root: usize,
+    }
+
+    impl OrbitMap {
+        fn intern(&mut self, name: &str) -> usize {
+            match self.ids.get(name) {
+                Some(&id) => id,
+                None => {
+                    self.names.push(String::from(name));
+                    self.parents.push(None);
+                    self.ids.insert(String::from(name), self.names.len() - 1);
+                    self.names.len() - 1
+                }
+            }
+        }
+
+        pub fn parse(s: &str) -> Result<OrbitMap, Error> {
+            OrbitMap::parse_with_root(s, ROOT)
+        }
+
+        pub fn parse_with_root(s: &str, root: &str) -> Result<OrbitMap, Error> {
+            let mut m = OrbitMap {
+                names: Vec::<String>::new(),
+                ids: HashMap::<String, usize>::new(),
+                parents: Vec::<Option<usize>>::new(),
+                root: 0,
+            };
+            for (i, line) in s.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let (parent, child) = read_orbit(line).map_err(|e| e.at_line(i + 1))?;
+                let parent = m.intern(parent);
+                let child = m.intern(child);
+                if m.parents[child].replace(parent).is_some() {
+                    let e = OrbitError::DuplicateChild(m.names[child].clone());
+                    return Err(Error::Orbit(e).at_line(i + 1));
+                }
+            }
+
+            let diagnostic = m.diagnose(root);
+            if diagnostic != Diagnostic::default() {
+                return Err(Error::Orbit(OrbitError::Invalid(diagnostic)));
+            }
+            m.root = m.ids.get(root).copied().unwrap_or(0);
+            Ok(m)
+        }
+
+        fn diagnose(&self, root_name: &str) -> Diagnostic {
+            const UNVISITED: u8 = 0;
+            const ON_WALK: u8 = 1;
+            const DONE: u8 = 2;
+
+            let mut diagnostic = Diagnostic::default();
+            let root = self
+                .ids
+                .get(root_name)
+                .copied()
+                .filter(|&id| self.parents[id].is_none());
+            if root.is_none() && !self.is_empty() {
+                diagnostic.missing_root = Some(String::from(root_name));
+            }
+
+            // Walk up from every body, marking the bodies of the current walk; meeting one of
+            // them again closes a cycle.
+            let mut state = vec![UNVISITED; self.len()];
+            let mut in_cycle = vec![false; self.len()];
+            let mut walk = Vec::<usize>::new();
+            for start in 0..self.len() {
+                let mut body = start;
+                while state[body] == UNVISITED {
+                    state[body] = ON_WALK;
+                    walk.push(body);
+                    match self.parents[body] {
+                        Some(parent) => body = parent,
+                        None => break,
+                    }
+                }
+                if state[body] == ON_WALK && self.parents[body].is_some() {
+                    let from = walk.iter().position(|&id| id == body).unwrap_or(0);
+                    for &id in &walk[from..] {
+                        in_cycle[id] = true;
+                    }
+                }
+                for id in walk.drain(..) {
+                    state[id] = DONE;
+                }
+            }
+
+            let mut reached = vec![false; self.len()];
+            if let Some(root) = root {
+                let children = self.children();
+                let mut pending = vec![root];
+                while let Some(id) = pending.pop() {
+                    reached[id] = true;
+                    pending.extend(children[id].iter().copied());
+                }
+            }
+
+            let names = |filter: &dyn Fn(usize) -> bool| {
+                let mut names = (0..self.len())
+                    .filter(|&id| filter(id))
+                    .map(|id| self.names[id].clone())
+                    .collect::<Vec<String>>();
+                names.sort();
+                names
+            };
+            diagnostic.extra_roots = names(&|id| self.parents[id].is_none() && Some(id) != root);
+            diagnostic.cycles = names(&|id| in_cycle[id]);
+            diagnostic.unreachable =
+                names(&|id| !reached[id] && !in_cycle[id] && self.parents[id].is_some());
+            diagnostic
+        }
+
+        pub fn len(&self) -> usize {
+            self.names.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.names.is_empty()
+        }
+
+        pub fn root(&self) -> usize {
+            self.root
+        }
+
+        pub fn id(&self, name: &str) -> Result<usize, OrbitError> {
+            self.ids
+                .get(name)
+                .copied()
+                .ok_or_else(|| OrbitError::UnknownBody(String::from(name)))
+        }
+
+        pub fn name(&self, id: usize) -> &str {
+            &self.names[id]
+        }
+
+        pub fn parent(&self, id: usize) -> Option<usize> {
+            self.parents[id]
+        }
+
+        // Bodies from the root down to the body the given one orbits.
+        pub fn ancestors(&self, id: usize) -> Vec<usize> {
+            let mut ancestors = Vec::<usize>::new();
+            let mut body = id;
+            while let Some(parent) = self.parents[body] {
+                ancestors.push(parent);
+                body = parent;
+            }
+            ancestors.reverse();
+            ancestors
+        }
+
+        // Each walk up the tree stops at the first body whose depth is already known, so every
+        // body is visited a constant number of times.
+        pub fn depths(&self) -> Vec<usize> {
+            let mut depths = vec![None; self.len()];
+            let mut path = Vec::<usize>::new();
+            for start in 0..self.len() {
+                let mut body = start;
+                while depths[body].is_none() {
+                    match self.parents[body] {
+                        Some(parent) => {
+                            path.push(body);
+                            body = parent;
+                        }
+                        None => depths[body] = Some(0),
+                    }
+                }
+                let mut depth = depths[body].unwrap_or(0);
+                while let Some(body) = path.pop() {
+                    depth += 1;
+                    depths[body] = Some(depth);
+                }
+            }
+            depths.into_iter().map(|d| d.unwrap_or(0)).collect()
+        }
+
+        pub fn total_orbits(&self) -> usize {
+            self.depths().into_iter().sum()
+        }
+
+        // Bodies directly orbiting each body, ordered by name.
+        pub fn children(&self) -> Vec<Vec<usize>> {
+            let mut children = vec![Vec::<usize>::new(); self.len()];
+            for (id, parent) in self.parents.iter().enumerate() {
+                if let Some(parent) = parent {
+                    children[*parent].push(id);
+                }
+            }
+            for bodies in &mut children {
+                bodies.sort_by(|a, b| self.names[*a].cmp(&self.names[*b]));
+            }
+            children
+        }
+
+        // Number of bodies orbiting each body directly or indirectly.
+        pub fn subtree_sizes(&self) -> Vec<usize> {
+            let depths = self.depths();
+            let mut order = (0..self.len()).collect::<Vec<usize>>();
+            order.sort_by_key(|&id| std::cmp::Reverse(depths[id]));
+            let mut sizes = vec![0; self.len()];
+            for id in order {
+                if let Some(parent) = self.parents[id] {
+                    sizes[parent] += sizes[id] + 1;
+                }
+            }
+            sizes
+        }
+    }
+
+    pub mod viz {
+        use super::{OrbitError, OrbitMap, OrbitQueries};
+        use std::collections::HashSet;
+        use std::fmt::Write;
+
+        // Graphviz digraph with edges from each body to the bodies orbiting it. The path between
+        // two bodies, if given, is drawn in red.
+        pub fn to_dot(
+            map: &OrbitMap,
+            highlight: Option<(&str, &str)>,
+        ) -> Result<String, OrbitError> {
+            let mut on_path = HashSet::<&str>::new();
+            let mut path_edges = HashSet::<(&str, &str)>::new();
+            if let Some((a, b)) = highlight {
+                let queries = OrbitQueries::new(map);
+                let path = queries.path(a, b)?;
+                for pair in path.windows(2) {
+                    path_edges.insert((pair[0], pair[1]));
+                    path_edges.insert((pair[1], pair[0]));
+                }
+                on_path.extend(path);
+            }
+
+            let mut out = String::from("digraph orbits {\n");
+            for id in 0..map.len() {
+                let name = map.name(id);
+                if on_path.contains(name) {
+                    _ = writeln!(out, "    {name:?} [style=filled, fillcolor=red];");
+                }
+            }
+            for id in 0..map.len() {
+                if let Some(parent) = map.parent(id) {
+                    let (from, to) = (map.name(parent), map.name(id));
+                    if path_edges.contains(&(from, to)) {
+                        _ = writeln!(out, "    {from:?} -> {to:?} [color=red, penwidth=2];");
+                    } else {
+                        _ = writeln!(out, "    {from:?} -> {to:?};");
+                    }
+                }
+            }
+            out.push_str("}\n");
+            Ok(out)
+        }
+
+        // Indented tree below the root, each body followed by the number of bodies orbiting it.
+        pub fn to_tree(map: &OrbitMap) -> String {
+            let mut out = String::new();
+            if map.is_empty() {
+                return out;
+            }
+            let children = map.children();
+            let sizes = map.subtree_sizes();
+            let mut pending = vec![(map.root(), 0)];
+            while let Some((id, depth)) = pending.pop() {
+                _ = writeln!(
+                    out,
+                    "{}{} ({})",
+                    "  ".repeat(depth),
+                    map.name(id),
+                    sizes[id]
+                );
+                pending.extend(children[id].iter().rev().map(|&child| (child, depth + 1)));
+            }
+            out
+        }
+    }
+
+    // Binary lifting: `up[k][body]` is the ancestor 2^k levels above the body, or the root once
+    // the walk would leave the tree. Any two bodies are brought to the same depth and lifted
+    // together in O(log n) steps.
+    pub struct OrbitQueries<'a> {
+        map: &'a OrbitMap,
+        depths: Vec<usize>,
+        up: Vec<Vec<usize>>,
+    }
+
+    impl<'a> OrbitQueries<'a> {
+        pub fn new(map: &'a OrbitMap) -> OrbitQueries<'a> {
+            let depths = map.depths();
+            let levels = usize::BITS - depths.iter().max().copied().unwrap_or(0).leading_zeros();
+            let mut up = vec![(0..map.len())
+                .map(|id| map.parent(id).unwrap_or(id))
+                .collect::<Vec<usize>>()];
+            for k in 1..levels.max(1) as usize {
+                let next = up[k - 1].iter().map(|&mid| up[k - 1][mid]).collect();
+                up.push(next);
+            }
+            OrbitQueries { map, depths, up }
+        }
+
+        fn lift(&self, mut id: usize, levels: usize) -> usize {
+            for (k, up) in self.up.iter().enumerate() {
+                if levels >> k & 1 == 1 {
+                    id = up[id];
+                }
+            }
+            id
+        }
+
+        fn lca_id(&self, a: usize, b: usize) -> usize {
+            let (mut a, mut b) = if self.depths[a] >= self.depths[b] {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            a = self.lift(a, self.depths[a] - self.depths[b]);
+            if a == b {
+                return a;
+            }
+            for up in self.up.iter().rev() {
+                if up[a] != up[b] {
+                    a = up[a];
+                    b = up[b];
+                }
+            }
+            self.up[0][a]
+        }
+
+        fn distance_id(&self, a: usize, b: usize) -> usize {
+            self.depths[a] + self.depths[b] - 2 * self.depths[self.lca_id(a, b)]
+        }
+
+        // Names borrow from the map rather than from the queries, so they outlive queries built
+        // for a single lookup, as in `viz::to_dot`.
+        pub fn lca(&self, a: &str, b: &str) -> Result<&'a str, OrbitError> {
+            let (a, b) = (self.map.id(a)?, self.map.id(b)?);
+            Ok(self.map.name(self.lca_id(a, b)))
+        }
+
+        pub fn distance(&self, a: &str, b: &str) -> Result<usize, OrbitError> {
+            Ok(self.distance_id(self.map.id(a)?, self.map.id(b)?))
+        }
+
+        // Orbital transfers needed to move from the body `a` orbits to the body `b` orbits.
+        pub fn transfers(&self, a: &str, b: &str) -> Result<usize, OrbitError> {
+            let parent = |name: &str| {
+                self.map.id(name).and_then(|id| {
+                    self.map
+                        .parent(id)
+                        .ok_or_else(|| OrbitError::NotOrbiting(String::from(name)))
+                })
+            };
+            Ok(self.distance_id(parent(a)?, parent(b)?))
+        }
+
+        // Bodies on the way from `a` to `b`, both included.
+        pub fn path(&self, a: &str, b: &str) -> Result<Vec<&'a str>, OrbitError> {
+            let (a, b) = (self.map.id(a)?, self.map.id(b)?);
+            let lca = self.lca_id(a, b);
+            let climb = |mut id: usize| {
+                let mut ids = vec![id];
+                while id != lca {
+                    id = self.up[0][id];
+                    ids.push(id);
+                }
+                ids
+            };
+            let mut path = climb(a);
+            let mut descent = climb(b);
+            descent.pop();
+            path.extend(descent.into_iter().rev());
+            Ok(path.into_iter().map(|id| self.map.name(id)).collect())
+        }
+    }
+}
+
+pub mod asteroids {
+    use crate::error::Error;
+    use crate::math::gcd;
+    use std::cmp::Ordering;
+    use std::collections::{HashMap, HashSet};
+
+    // Column and row of an asteroid, rows growing downwards.
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Asteroid(pub i32, pub i32);
+
+    pub struct Map {
+        asteroids: Vec<Asteroid>,
+    }
+
+    // Asteroids in the same reduced direction from the station hide one another.
+    fn direction(from: Asteroid, to: Asteroid) -> (i32, i32) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let d = gcd(dx, dy);
+        (dx / d, dy / d)
+    }
+
+    // Orders directions clockwise starting from straight up. The first half turn has the
+    // directions right of the vertical and up itself, within a half the cross product decides.
+    fn clockwise(a: (i32, i32), b: (i32, i32)) -> Ordering {
+        let half = |(dx, dy): (i32, i32)| dx < 0 || (dx == 0 && dy > 0);
+        half(a)
+            .cmp(&half(b))
+            .then_with(|| 0.cmp(&(a.0 * b.1 - a.1 * b.0)))
+    }
+
+    impl Map {
+        // Cells are '#' for an asteroid and '.' for empty space; 'X' marks an asteroid
+        // chosen as the station in the puzzle text.
+        pub fn parse(s: &str) -> Result<Map, Error> {
+            let mut asteroids = Vec::<Asteroid>::new();
+            let mut width = None;
+            for (y, line) in s.lines().map(|line| line.trim()).enumerate() {
+                if line.is_empty() {
+                    continue;
+                }
+                if *width.get_or_insert(line.len()) != line.len() {
+                    return Err(Error::syntax("rows of equal width", line).at_line(y + 1));
+                }
+                for (x, c) in line.chars().enumerate() {
+                    match c {
+                        '#' | 'X' => asteroids.push(Asteroid(x as i32, y as i32)),
+                        '.' => {}
+                        _ => {
+                            return Err(Error::syntax("'#' or '.'", &c.to_string())
+                                .at_column(x + 1)
+                                .at_line(y + 1))
+                        }
+                    }
+                }
+            }
+            Ok(Map { asteroids })
+        }
+
+        pub fn asteroids(&self) -> &[Asteroid] {
+            &self.asteroids
+        }
+
+        pub fn visible_from(&self, station: Asteroid) -> usize {
+            self.asteroids
+                .iter()
+                .filter(|&&a| a != station)
+                .map(|&a| direction(station, a))
+                .collect::<HashSet<(i32, i32)>>()
+                .len()
+        }
+
+        // The asteroid detecting the most others, ties going to the first in reading order.
+        pub fn best_station(&self) -> Option<(Asteroid, usize)> {
+            self.asteroids
+                .iter()
+                .map(|&a| (a, self.visible_from(a)))
+                .rev()
+                .max_by_key(|&(_, visible)| visible)
+        }
+
+        // The laser starts pointing up and turns clockwise, vaporising only the nearest
+        // asteroid in each direction per rotation.
+        pub fn vaporisation_order(&self, station: Asteroid) -> Vec<Asteroid> {
+            let mut rays = HashMap::<(i32, i32), Vec<Asteroid>>::new();
+            for &a in self.asteroids.iter().filter(|&&a| a != station) {
+                rays.entry(direction(station, a)).or_default().push(a);
+            }
+            let mut directions = rays.keys().copied().collect::<Vec<(i32, i32)>>();
+            directions.sort_by(|&a, &b| clockwise(a, b));
+
+            let mut order = Vec::<(usize, usize, Asteroid)>::new();
+            for (turn, d) in directions.iter().enumerate() {
+                let ray = rays.get_mut(d).unwrap();
+                ray.sort_by_key(|a| (a.0 - station.0).abs() + (a.1 - station.1).abs());
+                for (rotation, &a) in ray.iter().enumerate() {
+                    order.push((rotation, turn, a));
+                }
+            }
+            order.sort();
+            order.into_iter().map(|(_, _, a)| a).collect()
+        }
+    }
+}
+
+pub mod hull {
+    use crate::error::Error;
+    use crate::intcode::{Computer, HaltReason};
+    use std::collections::{HashMap, VecDeque};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Colour {
+        Black,
+        White,
+    }
+
+    // The brain answers every camera reading with a colour to paint and a turn, 0 for left
+    // and 1 for right, after which the robot moves one panel forward. Rows grow downwards.
+    pub struct Robot {
+        brain: Computer,
+        outputs: VecDeque<i64>,
+        start: Colour,
+        position: (i32, i32),
+        facing: (i32, i32),
+        panels: HashMap<(i32, i32), Colour>,
+    }
+
+    impl Robot {
+        pub fn new(code: &[i64], start: Colour) -> Robot {
+            Robot {
+                brain: Computer::new(code, vec![]),
+                outputs: VecDeque::<i64>::new(),
+                start,
+                position: (0, 0),
+                facing: (0, -1),
+                panels: HashMap::<(i32, i32), Colour>::new(),
+            }
+        }
+
+        pub fn colour(&self, panel: (i32, i32)) -> Colour {
+            match self.panels.get(&panel) {
+                Some(&colour) => colour,
+                None if panel == (0, 0) => self.start,
+                None => Colour::Black,
+            }
+        }
+
+        fn execute(&mut self, paint: i64, turn: i64) -> Result<(), Error> {
+            let colour = match paint {
+                0 => Colour::Black,
+                1 => Colour::White,
+                _ => return Err(Error::Solve(format!("cannot paint colour {paint}"))),
+            };
+            self.panels.insert(self.position, colour);
+            let (dx, dy) = self.facing;
+            self.facing = match turn {
+                0 => (dy, -dx),
+                1 => (-dy, dx),
+                _ => return Err(Error::Solve(format!("cannot turn {turn}"))),
+            };
+            self.position = (
+                self.position.0 + self.facing.0,
+                self.position.1 + self.facing.1,
+            );
+            Ok(())
+        }
+
+        // Outputs are queued in the order the brain produced them so that a pair split across
+        // two runs is still read as paint then turn.
+        pub fn run(&mut self) -> Result<(), Error> {
+            loop {
+                let camera = match self.colour(self.position) {
+                    Colour::Black => 0,
+                    Colour::White => 1,
+                };
+                self.brain.push_input(camera);
+                let halt_reason = self.brain.run_adv()?;
+                self.outputs.extend(self.brain.take_output());
+                while self.outputs.len() >= 2 {
+                    let paint = self.outputs.pop_front().unwrap();
+                    let turn = self.outputs.pop_front().unwrap();
+                    self.execute(paint, turn)?;
+                }
+                if halt_reason == HaltReason::Terminate {
+                    return Ok(());
+                }
+            }
+        }
+
+        // Panels painted at least once, whatever their colour now.
+        pub fn painted(&self) -> usize {
+            self.panels.len()
+        }
+
+        // White panels as '#' within their bounding box.
+        pub fn render(&self) -> String {
+            let white = self
+                .panels
+                .iter()
+                .filter(|(_, &colour)| colour == Colour::White)
+                .map(|(&panel, _)| panel)
+                .collect::<Vec<(i32, i32)>>();
+            let (Some(min_x), Some(max_x)) = (
+                white.iter().map(|p| p.0).min(),
+                white.iter().map(|p| p.0).max(),
+            ) else {
+                return String::new();
+            };
+            let min_y = white.iter().map(|p| p.1).min().unwrap_or(0);
+            let max_y = white.iter().map(|p| p.1).max().unwrap_or(0);
+            (min_y..=max_y)
+                .map(|y| {
+                    (min_x..=max_x)
+                        .map(|x| match self.colour((x, y)) {
+                            Colour::White => '#',
+                            Colour::Black => ' ',
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+    }
+}
+
+pub mod math {
+    use std::ops::{Div, Mul, Rem, Sub};
+
+    fn abs<T>(a: T) -> T
+    where
+        T: Copy + PartialOrd + Default + Sub<Output = T>,
+    {
+        if a < T::default() {
+            T::default() - a
+        } else {
+            a
+        }
+    }
+
+    // Never negative; gcd(0, 0) is 0.
+    pub fn gcd<T>(a: T, b: T) -> T
+    where
+        T: Copy + PartialOrd + Default + Rem<Output = T> + Sub<Output = T>,
+    {
+        let (mut a, mut b) = (abs(a), abs(b));
+        while b != T::default() {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    // Never negative; zero if either argument is.
+    pub fn lcm<T>(a: T, b: T) -> T
+    where
+        T: Copy
+            + PartialOrd
+            + Default
+            + Rem<Output = T>
+            + Sub<Output = T>
+            + Div<Output = T>
+            + Mul<Output = T>,
+    {
+        if a == T::default() || b == T::default() {
+            return T::default();
+        }
+        abs(a) / gcd(a, b) * abs(b)
+    }
+
+    // As lcm, but None if the result does not fit.
+    pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+        if a == 0 || b == 0 {
+            return Some(0);
+        }
+        (a / gcd(a, b)).checked_mul(b)
+    }
+}
+
+pub mod moons {
+    use crate::error::Error;
+    use crate::math::checked_lcm;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Moon {
+        pub position: [i64; 3],
+        pub velocity: [i64; 3],
+    }
+
+    impl Moon {
+        pub fn energy(&self) -> i64 {
+            let potential = self.position.iter().map(|p| p.abs()).sum::<i64>();
+            let kinetic = self.velocity.iter().map(|v| v.abs()).sum::<i64>();
+            potential * kinetic
+        }
+    }
+
+    // Parses `<x=-1, y=0, z=2>`, the moon starting at rest.
+    pub fn parse_moon(line: &str) -> Result<Moon, Error> {
+        let malformed = || Error::syntax("<x=.., y=.., z=..>", line);
+        let coordinates = line
+            .trim()
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .ok_or_else(malformed)?;
+        let mut position = [0; 3];
+        let mut it = coordinates.split(',');
+        for (axis, name) in ["x=", "y=", "z="].iter().enumerate() {
+            let value = it
+                .next()
+                .and_then(|s| s.trim().strip_prefix(name))
+                .ok_or_else(malformed)?;
+            position[axis] = value.parse::<i64>()?;
+        }
+        if it.next().is_some() {
+            return Err(malformed());
+        }
+        Ok(Moon {
+            position,
+            velocity: [0; 3],
+        })
+    }
+
+    pub fn parse_moons(s: &str) -> Result<Vec<Moon>, Error> {
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| parse_moon(line).map_err(|e| e.at_line(i + 1)))
+            .collect()
+    }
+
+    // Gravity pulls every pair of moons one unit together, then velocities move the moons.
+    fn step_axis(moons: &mut [Moon], axis: usize) {
+        for i in 0..moons.len() {
+            for j in i + 1..moons.len() {
+                let pull = (moons[j].position[axis] - moons[i].position[axis]).signum();
+                moons[i].velocity[axis] += pull;
+                moons[j].velocity[axis] -= pull;
+            }
+        }
+        for moon in moons.iter_mut() {
+            moon.position[axis] += moon.velocity[axis];
+        }
+    }
+
+    pub fn step(moons: &mut [Moon]) {
+        for axis in 0..3 {
+            step_axis(moons, axis);
+        }
+    }
+
+    pub fn total_energy(moons: &[Moon]) -> i64 {
+        moons.iter().map(|moon| moon.energy()).sum()
+    }
+
+    fn axis_period(moons: &[Moon], axis: usize) -> u64 {
+        let mut current = Vec::<Moon>::from(moons);
+        let mut steps = 0_u64;
+        loop {
+            step_axis(&mut current, axis);
+            steps += 1;
+            if current.iter().zip(moons).all(|(a, b)| {
+                a.position[axis] == b.position[axis] && a.velocity[axis] == b.velocity[axis]
+            }) {
+                return steps;
+            }
+        }
+    }
+
+    // Steps until the moons first return to a previous state. A step can be undone, so the
+    // first repeated state is the initial one, and as the axes move independently that
+    // happens after the least common multiple of their own periods.
+    pub fn period(moons: &[Moon]) -> Result<u64, Error> {
+        (0..3)
+            .map(|axis| axis_period(moons, axis))
+            .try_fold(1, checked_lcm)
+            .ok_or_else(|| Error::Solve(String::from("period overflows u64")))
+    }
+}
+
+pub mod error {
+    use crate::intcode::ExecError;
+    use crate::orbits::OrbitError;
+    use crate::password::ParseRangeError;
+    use std::fmt;
+    use std::io;
+    use std::num::ParseIntError;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Context {
+        Day(u32),
+        File(String),
+        Line(usize),
+        Column(usize),
+    }
+
+    impl fmt::Display for Context {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Context::Day(day) => write!(f, "day {day:02}"),
+                Context::File(path) => write!(f, "{path}"),
+                Context::Line(line) => write!(f, "line {line}"),
+                Context::Column(column) => write!(f, "column {column}"),
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub enum Error {
+        Io(io::Error),
+        Int(ParseIntError),
+        Syntax {
+            expected: &'static str,
+            found: String,
+        },
+        Range(ParseRangeError),
+        Orbit(OrbitError),
+        Exec(ExecError),
+        Solve(String),
+        Context {
+            context: Context,
+            source: Box<Error>,
+        },
+    }
+
+    pub type Result<T> = std::result::Result<T, Error>;
+
+    // Reads a whole file, naming it in the error.
+    pub fn read_file(path: &str) -> Result<String> {
+        std::fs::read_to_string(path).map_err(|e| Error::from(e).in_file(path))
+    }
+
+    impl Error {
+        pub fn syntax(expected: &'static str, found: &str) -> Error {
+            Error::Syntax {
+                expected,
+                found: String::from(found),
+            }
+        }
+
+        pub fn context(self, context: Context) -> Error {
+            Error::Context {
+                context,
+                source: Box::new(self),
+            }
+        }
+
+        // Line and column numbers are 1-based.
+        pub fn at_line(self, line: usize) -> Error {
+            self.context(Context::Line(line))
+        }
+
+        pub fn at_column(self, column: usize) -> Error {
+            self.context(Context::Column(column))
+        }
+
+        pub fn on_day(self, day: u32) -> Error {
+            self.context(Context::Day(day))
+        }
+
+        pub fn in_file(self, path: &str) -> Error {
+            self.context(Context::File(String::from(path)))
+        }
+
+        // The error with all its context stripped.
+        pub fn cause(&self) -> &Error {
+            match self {
+                Error::Context { source, .. } => source.cause(),
+                _ => self,
+            }
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Error::Io(e) => write!(f, "cannot read input: {e}"),
+                Error::Int(e) => write!(f, "invalid number: {e}"),
+                Error::Syntax { expected, found } => {
+                    write!(f, "expected {expected}, found {found:?}")
+                }
+                Error::Range(e) => write!(f, "{e}"),
+                Error::Orbit(e) => write!(f, "{e}"),
+                Error::Exec(_) => write!(f, "intcode program faulted"),
+                Error::Solve(message) => write!(f, "{message}"),
+                // Nested contexts read as one location, e.g. "day 03, line 2, column 7: ...".
+                Error::Context { context, source } => match **source {
+                    Error::Context { .. } => write!(f, "{context}, {source}"),
+                    _ => write!(f, "{context}: {source}"),
+                },
+            }
+        }
+    }
+
+    impl std::error::Error for Error {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Error::Io(e) => Some(e),
+                Error::Int(e) => Some(e),
+                Error::Context { source, .. } => Some(source.as_ref()),
+                _ => None,
+            }
+        }
+    }
+
+    impl From<io::Error> for Error {
+        fn from(e: io::Error) -> Error {
+            Error::Io(e)
+        }
+    }
+
+    impl From<ParseIntError> for Error {
+        fn from(e: ParseIntError) -> Error {
+            Error::Int(e)
+        }
+    }
+
+    impl From<ParseRangeError> for Error {
+        fn from(e: ParseRangeError) -> Error {
+            Error::Range(e)
+        }
+    }
+
+    impl From<OrbitError> for Error {
+        fn from(e: OrbitError) -> Error {
+            Error::Orbit(e)
+        }
+    }
+
+    impl From<ExecError> for Error {
+        fn from(e: ExecError) -> Error {
+            Error::Exec(e)
+        }
+    }
+}
+
+pub mod solution {
+    pub use crate::error::Result;
+    use std::fs;
+    use std::io::{read_to_string, stdin};
+    use std::process::exit;
+
+    pub trait Solution {
+        const DAY: u32;
+
+        fn part1(input: &str) -> Result<String>;
+        fn part2(input: &str) -> Result<String>;
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Part {
+        One,
+        Two,
+    }
+
+    pub type Solver = fn(&str) -> Result<String>;
+
+    #[derive(Clone, Copy)]
+    pub struct Day {
+        pub day: u32,
+        pub part1: Solver,
+        pub part2: Solver,
+    }
+
+    impl Day {
+        pub fn of<S: Solution>() -> Day {
+            Day {
+                day: S::DAY,
+                part1: S::part1,
+                part2: S::part2,
+            }
+        }
+
+        pub fn solver(&self, part: Part) -> Solver {
+            match part {
+                Part::One => self.part1,
+                Part::Two => self.part2,
+            }
+        }
+
+        // Path and contents of the input to time a part on: the personal puzzle input under
+        // .inputs/ if there is one, else the last example registered for the part in answers/.
+        // Paths are relative to the repository root.
+        pub fn timing_input(&self, part: Part) -> Option<(String, String)> {
+            let personal = format!(".inputs/day{:02}.txt", self.day);
+            let registry =
+                fs::read_to_string(format!("answers/day{:02}.txt", self.day)).unwrap_or_default();
+            let number = if part == Part::One { "1" } else { "2" };
+            let example = registry
+                .lines()
+                .filter_map(|line| {
+                    let mut fields = line.split(' ');
+                    let path = fields.next()?;
+                    (fields.next() == Some(number)).then_some(path)
+                })
+                .next_back()
+                .map(String::from);
+            [Some(personal), example]
+                .into_iter()
+                .flatten()
+                .find_map(|path| {
+                    let input = fs::read_to_string(&path).ok()?;
+                    Some((path, input))
+                })
+        }
+    }
+
+    // Entry point of the per-day binaries: solves the puzzle input read from stdin.
+    pub fn main<S: Solution>(part: Part) {
+        let input = read_to_string(stdin()).unwrap_or_else(|e| {
+            eprintln!("cannot read input: {e}");
+            exit(1)
+        });
+        let answer = match part {
+            Part::One => S::part1(&input),
+            Part::Two => S::part2(&input),
+        };
+        match answer {
+            Ok(answer) => println!("{answer}"),
+            Err(e) => {
+                eprintln!("error: {}", e.on_day(S::DAY));
+                exit(1)
+            }
+        }
+    }
+}
+
+pub mod days {
+    use crate::asteroids;
+    use crate::error::Error;
+    use crate::hull::{Colour, Robot};
+    use crate::intcode::{self, Computer, HaltReason, SelfTest};
+    use crate::moons;
+    use crate::orbits::{OrbitMap, OrbitQueries};
+    use crate::password::{self, Rules};
+    use crate::solution::{Day, Result, Solution};
+    use crate::wires::{self, Wire};
+    use crate::{fuel, input};
+    use itertools::Itertools;
+
+    pub fn all() -> Vec<Day> {
+        vec![
+            Day::of::<Day01>(),
+            Day::of::<Day02>(),
+            Day::of::<Day03>(),
+            Day::of::<Day04>(),
+            Day::of::<Day05>(),
+            Day::of::<Day06>(),
+            Day::of::<Day07>(),
+            Day::of::<Day08>(),
+            Day::of::<Day09>(),
+            Day::of::<Day10>(),
+            Day::of::<Day11>(),
+            Day::of::<Day12>(),
+        ]
+    }
+
+    pub struct Day01;
+
+    impl Day01 {
+        fn solve(input: &str, fuel: fn(u128) -> u128) -> Result<String> {
+            let masses = input::parse_values::<u128>(input)?;
+            fuel::checked_sum(masses.into_iter().map(fuel))
+                .map(|total| total.to_string())
+                .ok_or_else(|| Error::Solve(String::from("total fuel overflows")))
+        }
+    }
+
+    impl Solution for Day01 {
+        const DAY: u32 = 1;
+
+        fn part1(input: &str) -> Result<String> {
+            Day01::solve(input, fuel::module_fuel)
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            Day01::solve(input, fuel::total_fuel)
+        }
+    }
+
+    pub struct Day02;
+
+    impl Day02 {
+        fn run(code: &[i64], noun: i64, verb: i64) -> Result<i64> {
+            let mut memory = Vec::<i64>::from(code);
+            if memory.len() < 3 {
+                return Err(Error::Solve(String::from("program is too short")));
+            }
+            memory[1] = noun;
+            memory[2] = verb;
+            let mut computer = Computer::new(&memory, vec![]);
+            computer.run()?;
+            Ok(computer.memory()[0])
+        }
+    }
+
+    impl Solution for Day02 {
+        const DAY: u32 = 2;
+
+        fn part1(input: &str) -> Result<String> {
+            let code = intcode::parse_code(input)?;
+            Day02::run(&code, 12, 2).map(|i| i.to_string())
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            let code = intcode::parse_code(input)?;
+            for noun in 0..=99 {
+                for verb in 0..=99 {
+                    if Day02::run(&code, noun, verb)? == 19690720 {
+                        return Ok((100 * noun + verb).to_string());
+                    }
+                }
+            }
+            Err(Error::Solve(String::from(
+                "no noun and verb produce 19690720",
+            )))
+        }
+    }
+
+    pub struct Day03;
+
+    impl Day03 {
+        fn crossings(input: &str) -> Result<wires::Crossings> {
+            let wires = input
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| line.parse::<Wire>().map_err(|e| e.at_line(i + 1)))
+                .collect::<Result<Vec<Wire>>>()?;
+            Ok(wires::crossings(&wires.iter().collect::<Vec<&Wire>>(), 2))
+        }
+    }
+
+    impl Solution for Day03 {
+        const DAY: u32 = 3;
+
+        fn part1(input: &str) -> Result<String> {
+            Day03::crossings(input)?
+                .closest
+                .map(|i| i.distance.to_string())
+                .ok_or_else(|| Error::Solve(String::from("wires do not cross")))
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            Day03::crossings(input)?
+                .fewest_steps
+                .map(|i| i.steps.to_string())
+                .ok_or_else(|| Error::Solve(String::from("wires do not cross")))
+        }
+    }
+
+    pub struct Day04;
+
+    impl Solution for Day04 {
+        const DAY: u32 = 4;
+
+        fn part1(input: &str) -> Result<String> {
+            let range = password::parse_range(input, true)?;
+            Ok(password::count(range, &Rules::part1()).to_string())
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            let range = password::parse_range(input, true)?;
+            Ok(password::count(range, &Rules::part2()).to_string())
+        }
+    }
+
+    pub struct Day05;
+
+    impl Day05 {
+        // The diagnostic program outputs zero for every passing test and finally the code.
+        fn diagnose(input: &str, system: i64) -> Result<String> {
+            let code = intcode::parse_code(input)?;
+            let mut computer = Computer::new(&code, vec![system]);
+            computer.run()?;
+            match computer.output().split_last() {
+                Some((code, tests)) if tests.iter().all(|&t| t == 0) => Ok(code.to_string()),
+                Some(_) => Err(Error::Solve(format!(
+                    "diagnostic tests failed: {:?}",
+                    computer.output()
+                ))),
+                None => Err(Error::Solve(String::from("program produced no output"))),
+            }
+        }
+    }
+
+    impl Solution for Day05 {
+        const DAY: u32 = 5;
+
+        fn part1(input: &str) -> Result<String> {
+            Day05::diagnose(input, 1)
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            Day05::diagnose(input, 5)
+        }
+    }
+
+    pub struct Day06;
+
+    impl Solution for Day06 {
+        const DAY: u32 = 6;
+
+        fn part1(input: &str) -> Result<String> {
+            let m = OrbitMap::parse(input)?;
+            Ok(m.total_orbits().to_string())
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            let m = OrbitMap::parse(input)?;
+            OrbitQueries::new(&m)
+                .transfers("YOU", "SAN")
+                .map(|transfers| transfers.to_string())
+                .map_err(Error::from)
+        }
+    }
+
+    pub struct Day07;
+
+    impl Day07 {
+        fn chain(code: &[i64], phases: &[i64]) -> Result<i64> {
+            let mut signal = 0;
+            for phase in phases {
+                let mut computer = Computer::new(code, vec![*phase, signal]);
+                computer.run()?;
+                signal = *computer
+                    .output()
+                    .first()
+                    .ok_or_else(|| Error::Solve(String::from("There is no output")))?;
+            }
+            Ok(signal)
+        }
+
+        fn feedback_loop(code: &[i64], phases: &[i64]) -> Result<i64> {
+            let mut computers = phases
+                .iter()
+                .map(|ph| Computer::new(code, vec![*ph]))
+                .collect::<Vec<Computer>>();
+            let mut signal = 0;
+            let mut i = 0;
+            loop {
+                computers[i].push_input(signal);
+                let halt_reason = computers[i].run_adv()?;
+                signal = computers[i]
+                    .pop_output()
+                    .ok_or_else(|| Error::Solve(String::from("There is no output")))?;
+                i += 1;
+                if i == phases.len() {
+                    i = 0;
+                    if halt_reason == HaltReason::Terminate {
+                        return Ok(signal);
+                    }
+                }
+            }
+        }
+
+        fn max_signal(
+            input: &str,
+            phases: [i64; 5],
+            run: fn(&[i64], &[i64]) -> Result<i64>,
+        ) -> Result<String> {
+            let code = intcode::parse_code(input)?;
+            let mut max_signal = i64::MIN;
+            for phases_p in phases.iter().copied().permutations(phases.len()) {
+                max_signal = max_signal.max(run(&code, &phases_p)?);
+            }
+            Ok(max_signal.to_string())
+        }
+    }
+
+    impl Solution for Day07 {
+        const DAY: u32 = 7;
+
+        fn part1(input: &str) -> Result<String> {
+            Day07::max_signal(input, [0, 1, 2, 3, 4], Day07::chain)
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            Day07::max_signal(input, [5, 6, 7, 8, 9], Day07::feedback_loop)
+        }
+    }
+
+    pub struct Day08;
+
+    impl Day08 {
+        const WIDTH: usize = 25;
+        const HEIGHT: usize = 6;
+
+        fn count_occurences(cs: &[char], c: char) -> usize {
+            cs.iter().filter(|&other_c| *other_c == c).count()
+        }
+
+        fn layers(input: &str, width: usize, height: usize) -> Result<Vec<char>> {
+            let chars = input.trim().chars().collect::<Vec<char>>();
+            if chars.is_empty() || width * height == 0 || chars.len() % (width * height) != 0 {
+                return Err(Error::Solve(format!(
+                    "image of {} pixels does not split into {width}x{height} layers",
+                    chars.len()
+                )));
+            }
+            Ok(chars)
+        }
+
+        // Each pixel takes the colour of the topmost layer that is not transparent, drawn as #
+        // for white and a space for black.
+        pub fn decode(input: &str, width: usize, height: usize) -> Result<String> {
+            let chars = Day08::layers(input, width, height)?;
+            let layers = chars.chunks(width * height).collect::<Vec<&[char]>>();
+            let image = (0..width * height)
+                .map(
+                    |i| match layers.iter().map(|layer| layer[i]).find(|&c| c != '2') {
+                        Some('1') => '#',
+                        _ => ' ',
+                    },
+                )
+                .collect::<Vec<char>>();
+            Ok(image
+                .chunks(width)
+                .map(|row| row.iter().collect::<String>())
+                .collect::<Vec<String>>()
+                .join("\n"))
+        }
+    }
+
+    impl Solution for Day08 {
+        const DAY: u32 = 8;
+
+        fn part1(input: &str) -> Result<String> {
+            let chars = Day08::layers(input, Day08::WIDTH, Day08::HEIGHT)?;
+            let min_chunk = chars
+                .chunks(Day08::WIDTH * Day08::HEIGHT)
+                .min_by_key(|&chunk| Day08::count_occurences(chunk, '0'))
+                .unwrap_or_default();
+            let result =
+                Day08::count_occurences(min_chunk, '1') * Day08::count_occurences(min_chunk, '2');
+            Ok(result.to_string())
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            Day08::decode(input, Day08::WIDTH, Day08::HEIGHT)
+        }
+    }
+
+    pub struct Day09;
+
+    impl Solution for Day09 {
+        const DAY: u32 = 9;
+
+        // BOOST in test mode outputs its keycode only when every opcode works.
+        fn part1(input: &str) -> Result<String> {
+            let code = intcode::parse_code(input)?;
+            match intcode::self_test(&code)? {
+                SelfTest::Passed(keycode) => Ok(keycode.to_string()),
+                SelfTest::Malfunctions(opcodes) => Err(Error::Solve(format!(
+                    "malfunctioning opcodes: {}",
+                    opcodes.iter().map(|o| o.to_string()).join(", ")
+                ))),
+                SelfTest::NoOutput => Err(Error::Solve(String::from(
+                    "the self test halted without output",
+                ))),
+            }
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            let code = intcode::parse_code(input)?;
+            let mut computer = Computer::new(&code, vec![2]);
+            computer.run()?;
+            match computer.output() {
+                [coordinates] => Ok(coordinates.to_string()),
+                output => Err(Error::Solve(format!(
+                    "expected a single output, got {output:?}"
+                ))),
+            }
+        }
+    }
+
+    pub struct Day10;
+
+    impl Day10 {
+        fn best_station(input: &str) -> Result<(asteroids::Map, asteroids::Asteroid, usize)> {
+            let map = asteroids::Map::parse(input)?;
+            let (station, visible) = map
+                .best_station()
+                .ok_or_else(|| Error::Solve(String::from("there are no asteroids")))?;
+            Ok((map, station, visible))
+        }
+    }
+
+    impl Solution for Day10 {
+        const DAY: u32 = 10;
+
+        fn part1(input: &str) -> Result<String> {
+            Day10::best_station(input).map(|(_, _, visible)| visible.to_string())
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            let (map, station, _) = Day10::best_station(input)?;
+            map.vaporisation_order(station)
+                .get(199)
+                .map(|a| (100 * a.0 + a.1).to_string())
+                .ok_or_else(|| Error::Solve(String::from("fewer than 200 asteroids to vaporise")))
+        }
+    }
+
+    pub struct Day11;
+
+    impl Day11 {
+        fn paint(input: &str, start: Colour) -> Result<Robot> {
+            let code = intcode::parse_code(input)?;
+            let mut robot = Robot::new(&code, start);
+            robot.run()?;
+            Ok(robot)
+        }
+    }
+
+    impl Solution for Day11 {
+        const DAY: u32 = 11;
+
+        fn part1(input: &str) -> Result<String> {
+            Day11::paint(input, Colour::Black).map(|robot| robot.painted().to_string())
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            Day11::paint(input, Colour::White).map(|robot| robot.render())
+        }
+    }
+
+    pub struct Day12;
+
+    impl Solution for Day12 {
+        const DAY: u32 = 12;
+
+        fn part1(input: &str) -> Result<String> {
+            let mut moons = moons::parse_moons(input)?;
+            for _ in 0..1000 {
+                moons::step(&mut moons);
+            }
+            Ok(moons::total_energy(&moons).to_string())
+        }
+
+        fn part2(input: &str) -> Result<String> {
+            let moons = moons::parse_moons(input)?;
+            Ok(moons::period(&moons)?.to_string())
+        }
     }
 }
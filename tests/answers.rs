@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::Path;
+
+use aoc19::days;
+use aoc19::solution::Part;
+
+// Days whose puzzle texts give no example input with an answer.
+const UNREGISTERED: [u32; 3] = [2, 4, 8];
+
+struct Entry {
+    line: usize,
+    input: String,
+    part: Part,
+    expected: String,
+}
+
+fn parse_registry(text: &str) -> Vec<Entry> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|(n, l)| {
+            let mut fields = l.splitn(3, ' ');
+            let input = fields.next().unwrap();
+            let part = match fields.next() {
+                Some("1") => Part::One,
+                Some("2") => Part::Two,
+                other => panic!("line {}: bad part {:?}", n + 1, other),
+            };
+            let expected = fields
+                .next()
+                .unwrap_or_else(|| panic!("line {}: missing answer", n + 1))
+                .replace("\\n", "\n");
+            Entry {
+                line: n + 1,
+                input: String::from(input),
+                part,
+                expected,
+            }
+        })
+        .collect()
+}
+
+// Only the lines that differ, with their numbers.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<&str>>();
+    let actual = actual.lines().collect::<Vec<&str>>();
+    let mut out = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let (expected, actual) = (expected.get(i), actual.get(i));
+        if expected == actual {
+            continue;
+        }
+        out += &format!("  line {}:\n", i + 1);
+        if let Some(line) = expected {
+            out += &format!("  - {line}\n");
+        }
+        if let Some(line) = actual {
+            out += &format!("  + {line}\n");
+        }
+    }
+    out
+}
+
+#[test]
+fn registered_answers_match() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut checked = 0;
+    let mut failures = Vec::new();
+
+    for day in days::all() {
+        let registry = format!("answers/day{:02}.txt", day.day);
+        let text = fs::read_to_string(root.join(&registry));
+        if UNREGISTERED.contains(&day.day) {
+            assert!(
+                text.is_err(),
+                "{registry} exists but day {} is unregistered",
+                day.day
+            );
+            continue;
+        }
+        let text = text.unwrap_or_else(|e| panic!("{registry}: {e}"));
+        for entry in parse_registry(&text) {
+            let Ok(input) = fs::read_to_string(root.join(&entry.input)) else {
+                eprintln!(
+                    "skipping {registry}:{}: {} not found",
+                    entry.line, entry.input
+                );
+                continue;
+            };
+            checked += 1;
+            let header = format!(
+                "{registry}:{} ({} part {})",
+                entry.line,
+                entry.input,
+                if entry.part == Part::One { 1 } else { 2 }
+            );
+            match day.solver(entry.part)(&input) {
+                Ok(actual) if actual.trim_end() == entry.expected => {}
+                Ok(actual) => {
+                    failures.push(format!("{header}\n{}", diff(&entry.expected, &actual)))
+                }
+                Err(e) => failures.push(format!("{header}\n  error: {e}\n")),
+            }
+        }
+    }
+
+    assert!(checked > 0, "no registered answers were checked");
+    assert!(
+        failures.is_empty(),
+        "{} of {checked} answers changed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn diff_shows_only_changed_lines() {
+    assert_eq!(diff("a\nb\nc", "a\nb\nc"), "");
+    assert_eq!(diff("a\nb\nc", "a\nx\nc"), "  line 2:\n  - b\n  + x\n");
+    assert_eq!(diff("a", "a\nb"), "  line 2:\n  + b\n");
+    assert_eq!(diff("a\nb", "a"), "  line 2:\n  - b\n");
+}
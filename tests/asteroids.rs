@@ -0,0 +1,82 @@
+mod common;
+
+use aoc19::asteroids::{Asteroid, Map};
+
+fn example(name: &str) -> Map {
+    Map::parse(&common::example(name)).unwrap()
+}
+
+#[test]
+fn counts_visible_asteroids() {
+    let map = example("day10-1.txt");
+    let counts = map
+        .asteroids()
+        .iter()
+        .map(|&a| map.visible_from(a))
+        .collect::<Vec<usize>>();
+    assert_eq!(counts, vec![7, 7, 6, 7, 7, 7, 5, 7, 8, 7]);
+}
+
+#[test]
+fn finds_best_station() {
+    let cases = [
+        ("day10-1.txt", Asteroid(3, 4), 8),
+        ("day10-2.txt", Asteroid(5, 8), 33),
+        ("day10-3.txt", Asteroid(1, 2), 35),
+        ("day10-4.txt", Asteroid(6, 3), 41),
+        ("day10-5.txt", Asteroid(11, 13), 210),
+    ];
+    for (name, station, visible) in cases {
+        assert_eq!(
+            example(name).best_station(),
+            Some((station, visible)),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn vaporises_clockwise_from_up() {
+    let order = example("day10-6.txt").vaporisation_order(Asteroid(8, 3));
+    assert_eq!(
+        order[..9],
+        [
+            Asteroid(8, 1),
+            Asteroid(9, 0),
+            Asteroid(9, 1),
+            Asteroid(10, 0),
+            Asteroid(9, 2),
+            Asteroid(11, 1),
+            Asteroid(12, 1),
+            Asteroid(11, 2),
+            Asteroid(15, 1),
+        ]
+    );
+}
+
+#[test]
+fn vaporises_the_200th_asteroid() {
+    let order = example("day10-5.txt").vaporisation_order(Asteroid(11, 13));
+    assert_eq!(order.len(), 299);
+    for (nth, asteroid) in [
+        (1, Asteroid(11, 12)),
+        (2, Asteroid(12, 1)),
+        (3, Asteroid(12, 2)),
+        (10, Asteroid(12, 8)),
+        (20, Asteroid(16, 0)),
+        (50, Asteroid(16, 9)),
+        (100, Asteroid(10, 16)),
+        (199, Asteroid(9, 6)),
+        (200, Asteroid(8, 2)),
+        (201, Asteroid(10, 9)),
+        (299, Asteroid(11, 1)),
+    ] {
+        assert_eq!(order[nth - 1], asteroid, "asteroid {nth}");
+    }
+}
+
+#[test]
+fn rejects_unknown_cells_and_ragged_rows() {
+    assert!(Map::parse("#.\n#a\n").is_err());
+    assert!(Map::parse("#.\n#\n").is_err());
+}
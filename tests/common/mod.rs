@@ -0,0 +1,5 @@
+// Reads an example input from answers/examples/.
+pub fn example(name: &str) -> String {
+    let path = format!("{}/answers/examples/{name}", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(path).unwrap()
+}
@@ -0,0 +1,44 @@
+use std::fs;
+use std::process::Command;
+
+use aoc19::days::Day08;
+
+#[test]
+fn image_takes_the_topmost_opaque_pixel() {
+    assert_eq!(Day08::decode("0222112222120000", 2, 2).unwrap(), " #\n# ");
+    // Transparent all the way down stays black.
+    assert_eq!(Day08::decode("2212", 2, 1).unwrap(), "# ");
+    assert_eq!(
+        Day08::decode("01201", 2, 2).unwrap_err().to_string(),
+        "image of 5 pixels does not split into 2x2 layers"
+    );
+    assert!(Day08::decode("", 2, 2).is_err());
+    assert!(Day08::decode("0", 0, 1).is_err());
+}
+
+#[test]
+fn run_all_fails_when_a_solution_fails() {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("aoc-run-all");
+    fs::create_dir_all(dir.join(".inputs")).unwrap();
+    let run_all = || {
+        Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["run", "--all"])
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+
+    fs::write(dir.join(".inputs/day01.txt"), "12\n14\n").unwrap();
+    let output = run_all();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("day 01 part One"));
+
+    fs::write(dir.join(".inputs/day01.txt"), "twelve\n").unwrap();
+    let output = run_all();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("line 1: expected u128, found \"twelve\""));
+}
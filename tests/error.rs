@@ -0,0 +1,96 @@
+use aoc19::error::{self, Context, Error};
+use aoc19::intcode::ExecError;
+use aoc19::orbits::OrbitError;
+use aoc19::password::ParseRangeError;
+use std::error::Error as _;
+use std::io;
+
+#[test]
+fn each_variant_has_its_message() {
+    let io = Error::from(io::Error::other("disk on fire"));
+    assert_eq!(io.to_string(), "cannot read input: disk on fire");
+
+    let int = Error::from("12x".parse::<i32>().unwrap_err());
+    assert_eq!(
+        int.to_string(),
+        "invalid number: invalid digit found in string"
+    );
+
+    assert_eq!(
+        Error::syntax("u8", "256").to_string(),
+        "expected u8, found \"256\""
+    );
+    assert_eq!(
+        Error::from(ParseRangeError::Reversed).to_string(),
+        "lower bound exceeds upper bound"
+    );
+    assert_eq!(
+        Error::from(OrbitError::UnknownBody(String::from("X"))).to_string(),
+        "unknown body X"
+    );
+    assert_eq!(
+        Error::from(ExecError).to_string(),
+        "intcode program faulted"
+    );
+    assert_eq!(
+        Error::Solve(String::from("no answer")).to_string(),
+        "no answer"
+    );
+}
+
+#[test]
+fn contexts_read_as_one_location() {
+    let e = Error::syntax("a digit", "x")
+        .at_column(7)
+        .at_line(2)
+        .in_file("input.txt")
+        .on_day(3);
+    assert_eq!(
+        e.to_string(),
+        "day 03, input.txt, line 2, column 7: expected a digit, found \"x\""
+    );
+    assert_eq!(
+        Error::Solve(String::from("stuck")).on_day(12).to_string(),
+        "day 12: stuck"
+    );
+}
+
+#[test]
+fn cause_strips_every_context() {
+    let e = Error::Solve(String::from("stuck")).at_line(1).on_day(1);
+    assert!(matches!(e.cause(), Error::Solve(m) if m == "stuck"));
+    assert!(matches!(
+        e,
+        Error::Context {
+            context: Context::Day(1),
+            ..
+        }
+    ));
+
+    let bare = Error::from(ExecError);
+    assert!(matches!(bare.cause(), Error::Exec(_)));
+}
+
+#[test]
+fn sources_follow_the_context_chain() {
+    let e = Error::from("".parse::<u8>().unwrap_err()).at_line(4);
+    let inner = e.source().unwrap();
+    assert_eq!(
+        inner.to_string(),
+        "invalid number: cannot parse integer from empty string"
+    );
+    assert_eq!(
+        inner.source().unwrap().to_string(),
+        "cannot parse integer from empty string"
+    );
+    assert!(Error::Solve(String::new()).source().is_none());
+}
+
+#[test]
+fn read_file_names_the_missing_file() {
+    let e = error::read_file("no/such/file.txt").unwrap_err();
+    assert!(e
+        .to_string()
+        .starts_with("no/such/file.txt: cannot read input: "));
+    assert!(matches!(e.cause(), Error::Io(_)));
+}
@@ -0,0 +1,61 @@
+use aoc19::days::Day01;
+use aoc19::fuel::{self, FuelTable};
+use aoc19::solution::Solution;
+
+#[test]
+fn module_fuel_rounds_down_and_stops_at_zero() {
+    assert_eq!(fuel::module_fuel(12), 2);
+    assert_eq!(fuel::module_fuel(14), 2);
+    assert_eq!(fuel::module_fuel(1969), 654);
+    assert_eq!(fuel::module_fuel(100756), 33583);
+    assert_eq!(fuel::module_fuel(5), 0);
+}
+
+#[test]
+fn total_fuel_includes_fuel_for_the_fuel() {
+    assert_eq!(fuel::total_fuel(14), 2);
+    assert_eq!(fuel::total_fuel(1969), 966);
+    assert_eq!(fuel::total_fuel(100756), 50346);
+    assert_eq!(
+        fuel::total_fuel(u128::MAX),
+        170141183460469231731687303715884105451
+    );
+}
+
+#[test]
+fn table_agrees_with_the_rocket_equation() {
+    for bound in [0, 1, 9, 1000] {
+        let table = FuelTable::new(bound);
+        for mass in (0..5000).chain([1 << 40, u128::MAX]) {
+            assert_eq!(
+                table.total_fuel(mass),
+                fuel::total_fuel(mass),
+                "mass {mass} with bound {bound}"
+            );
+        }
+    }
+}
+
+#[test]
+fn sums_overflow_to_none() {
+    assert_eq!(fuel::checked_sum([]), Some(0));
+    assert_eq!(fuel::checked_sum([1, 2, 3]), Some(6));
+    assert_eq!(fuel::checked_sum([u128::MAX, 0]), Some(u128::MAX));
+    assert_eq!(fuel::checked_sum([u128::MAX, 1]), None);
+
+    let table = FuelTable::new(100);
+    assert_eq!(
+        table.batch_total(&[14, 1969, 100756]),
+        Some(2 + 966 + 50346)
+    );
+    assert_eq!(table.batch_total(&[u128::MAX; 3]), None);
+}
+
+#[test]
+fn masses_are_unsigned() {
+    assert_eq!(Day01::part1("12\n14\n").unwrap(), "4");
+    assert_eq!(
+        Day01::part1("12\n-14\n").unwrap_err().to_string(),
+        "line 2: expected u128, found \"-14\""
+    );
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use aoc19::hull::{Colour, Robot};
+use aoc19::intcode;
+
+// Answers each camera reading with the paint and turn instructions from the puzzle text.
+const EXAMPLE: &str = "day11-1.txt";
+
+fn run(program: &str, start: Colour) -> Robot {
+    let mut robot = Robot::new(&intcode::parse_code(program).unwrap(), start);
+    robot.run().unwrap();
+    robot
+}
+
+#[test]
+fn paints_the_example_panels() {
+    let robot = run(&common::example(EXAMPLE), Colour::Black);
+    assert_eq!(robot.painted(), 6);
+    assert_eq!(robot.colour((0, 0)), Colour::Black);
+    assert_eq!(robot.colour((1, -1)), Colour::White);
+    assert_eq!(robot.colour((0, -1)), Colour::Black);
+    assert_eq!(robot.render(), "  #\n  #\n## ");
+}
+
+#[test]
+fn reads_paint_before_turn_across_runs() {
+    // The paint instruction comes out before the brain asks for a second reading.
+    let robot = run("3,200,104,1,3,201,104,0,99", Colour::Black);
+    assert_eq!(robot.painted(), 1);
+    assert_eq!(robot.colour((0, 0)), Colour::White);
+    assert_eq!(robot.colour((-1, 0)), Colour::Black);
+}
+
+#[test]
+fn camera_sees_the_starting_panel() {
+    // Paints the panel with the colour the camera reports.
+    let program = "3,200,4,200,104,1,99";
+    assert_eq!(run(program, Colour::White).render(), "#");
+    assert_eq!(run(program, Colour::Black).render(), "");
+    assert_eq!(run(program, Colour::Black).painted(), 1);
+}
+
+#[test]
+fn rejects_unknown_instructions() {
+    let code = intcode::parse_code("3,200,104,2,104,0,99").unwrap();
+    assert!(Robot::new(&code, Colour::Black).run().is_err());
+    let code = intcode::parse_code("3,200,104,1,104,5,99").unwrap();
+    assert!(Robot::new(&code, Colour::Black).run().is_err());
+}
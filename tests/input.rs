@@ -0,0 +1,31 @@
+use aoc19::error::Error;
+use aoc19::input;
+
+#[test]
+fn skips_blank_lines_and_trims() {
+    let values = input::parse_values::<i32>("  12\n\n-3  \n\t\n7").unwrap();
+    assert_eq!(values, vec![12, -3, 7]);
+}
+
+#[test]
+fn empty_input_has_no_values() {
+    assert!(input::parse_values::<i32>("").unwrap().is_empty());
+    assert!(input::parse_values::<i32>("\n  \n").unwrap().is_empty());
+}
+
+#[test]
+fn reports_the_line_of_a_bad_token() {
+    let err = input::parse_values::<u8>("1\n\n256\n3").unwrap_err();
+    assert_eq!(err.to_string(), "line 3: expected u8, found \"256\"");
+    assert!(matches!(err.cause(), Error::Syntax { .. }));
+
+    let err = input::parse_values::<i32>("1 2").unwrap_err();
+    assert_eq!(err.to_string(), "line 1: expected i32, found \"1 2\"");
+}
+
+#[test]
+fn reports_the_line_of_a_read_error() {
+    let err = input::read_values::<i32, _>(&b"1\n\xff\n"[..]).unwrap_err();
+    assert!(err.to_string().starts_with("line 2: "));
+    assert!(matches!(err.cause(), Error::Io(_)));
+}
@@ -0,0 +1,379 @@
+mod common;
+
+use std::fs;
+use std::process::Command;
+
+use aoc19::intcode::ascii::{self, AsciiComputer, Output};
+use aoc19::intcode::transcript::{self, Divergence, Event, Recorder};
+use aoc19::intcode::{self, aot, Computer, HaltReason, SelfTest};
+
+const QUINE: &str = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+
+// Checks its input in relative mode, with large values and beyond the end of the program,
+// reporting the opcodes that misbehave before a keycode of 42.
+const SELF_TEST: &str = "day09-3.txt";
+
+// Compares input with 8, outputting 999, 1000 or 1001.
+const COMPARE: &str = "day05-3.txt";
+
+fn run(program: &str, input: Vec<i64>) -> Vec<i64> {
+    let mut computer = Computer::new(&intcode::parse_code(program).unwrap(), input);
+    computer.run().unwrap();
+    computer.take_output()
+}
+
+#[test]
+fn relative_mode_quine_outputs_itself() {
+    assert_eq!(run(QUINE, vec![]), intcode::parse_code(QUINE).unwrap());
+}
+
+#[test]
+fn values_are_64_bit() {
+    assert_eq!(
+        run("1102,34915192,34915192,7,4,7,99,0", vec![]),
+        vec![1219070632396864]
+    );
+    assert_eq!(
+        run("104,1125899906842624,99", vec![]),
+        vec![1125899906842624]
+    );
+}
+
+#[test]
+fn memory_grows_past_the_program() {
+    let code = intcode::parse_code("4,500,1101,1,2,1000,4,1000,99").unwrap();
+    let mut computer = Computer::new(&code, vec![]);
+    computer.run().unwrap();
+    assert_eq!(computer.output(), &[0, 3]);
+    assert_eq!(computer.memory().len(), 1001);
+}
+
+#[test]
+fn writes_past_the_memory_limit_fault() {
+    let code = intcode::parse_code("1101,1,2,1099511627776,99").unwrap();
+    assert!(Computer::new(&code, vec![]).run().is_err());
+    let code = format!("1101,1,2,{},4,{0},99", intcode::MEMORY_LIMIT - 1);
+    assert_eq!(run(&code, vec![]), vec![3]);
+}
+
+#[test]
+fn negative_addresses_fault() {
+    let code = intcode::parse_code("4,-1,99").unwrap();
+    assert!(Computer::new(&code, vec![]).run().is_err());
+    let code = intcode::parse_code("109,-5,204,0,99").unwrap();
+    assert!(Computer::new(&code, vec![]).run().is_err());
+}
+
+#[test]
+fn writes_cannot_be_immediate() {
+    let runs = |program: &str| {
+        let code = intcode::parse_code(program).unwrap();
+        Computer::new(&code, vec![1]).run().is_ok()
+    };
+    assert!(runs("3,3,99,0"));
+    assert!(runs("203,3,99,0"));
+    assert!(runs("21107,1,2,5,99,0"));
+    assert!(!runs("103,3,99"));
+    assert!(!runs("11101,1,1,5,99,0"));
+    // Modes for parameters an instruction does not have, or unknown modes, are rejected too.
+    assert!(!runs("10099"));
+    assert!(!runs("304,0,99"));
+}
+
+#[test]
+fn self_test_reports_keycode_or_malfunctions() {
+    let code = intcode::parse_code(&common::example(SELF_TEST)).unwrap();
+    assert!(matches!(
+        intcode::self_test(&code),
+        Ok(SelfTest::Passed(42))
+    ));
+    // Fed 2 instead of 1 the checks fail as they would on a broken computer.
+    assert_eq!(
+        run(&common::example(SELF_TEST), vec![2]),
+        vec![203, 22201, 209, 42]
+    );
+
+    let code = intcode::parse_code("104,203,104,1008,99").unwrap();
+    match intcode::self_test(&code) {
+        Ok(SelfTest::Malfunctions(opcodes)) => assert_eq!(opcodes, vec![203, 1008]),
+        _ => panic!("expected malfunctions"),
+    }
+
+    let code = intcode::parse_code("99").unwrap();
+    assert!(matches!(intcode::self_test(&code), Ok(SelfTest::NoOutput)));
+}
+
+#[test]
+fn ascii_output_splits_text_from_values() {
+    assert_eq!(
+        ascii::decode_output(&[104, 105, 10, 1000, 111, 107]),
+        vec![
+            Output::Text(String::from("hi\n")),
+            Output::Value(1000),
+            Output::Text(String::from("ok")),
+        ]
+    );
+    // Only 0 to 127 are ASCII; everything else, negative values included, stays a value.
+    assert_eq!(
+        ascii::decode_output(&[127, 128, -1, 0]),
+        vec![
+            Output::Text(String::from("\u{7f}")),
+            Output::Value(128),
+            Output::Value(-1),
+            Output::Text(String::from("\0")),
+        ]
+    );
+    assert!(ascii::decode_output(&[]).is_empty());
+}
+
+#[test]
+fn ascii_lines_end_in_a_newline() {
+    assert_eq!(
+        ascii::encode_line("NOT A J"),
+        vec![78, 79, 84, 32, 65, 32, 74, 10]
+    );
+    assert_eq!(ascii::encode_line(""), vec![10]);
+}
+
+#[test]
+fn ascii_computer_stops_at_a_prompt() {
+    // Prints "?\n", reads a character and prints it back after the newline is consumed.
+    let code = intcode::parse_code("104,63,104,10,3,100,3,101,4,100,99").unwrap();
+    let mut computer = AsciiComputer::new(&code);
+    let (reason, output) = computer.run().unwrap();
+    assert!(reason == HaltReason::Input);
+    assert_eq!(output, vec![Output::Text(String::from("?\n"))]);
+
+    computer.push_line("x");
+    let (reason, output) = computer.run().unwrap();
+    assert!(reason == HaltReason::Terminate);
+    assert_eq!(output, vec![Output::Text(String::from("x"))]);
+}
+
+// Doubles two inputs, printing each result before asking for the next.
+const DOUBLER: &str = "3,20,1002,20,2,20,4,20,3,20,1002,20,2,20,4,20,99";
+
+fn record(program: &str, inputs: &[i64]) -> Vec<Event> {
+    let mut recorder = Recorder::new(&intcode::parse_code(program).unwrap());
+    recorder.run_adv().unwrap();
+    for &i in inputs {
+        recorder.push_input(i);
+        recorder.run_adv().unwrap();
+    }
+    recorder.events().to_vec()
+}
+
+#[test]
+fn transcripts_round_trip_and_replay() {
+    let events = record(DOUBLER, &[3, -7]);
+    assert_eq!(
+        events,
+        vec![
+            Event::Input(3),
+            Event::Output(6),
+            Event::Input(-7),
+            Event::Output(-14)
+        ]
+    );
+    let text = transcript::format_transcript(&events);
+    assert_eq!(text, "in 3\nout 6\nin -7\nout -14\n");
+    assert_eq!(transcript::parse_transcript(&text).unwrap(), events);
+
+    let code = intcode::parse_code(DOUBLER).unwrap();
+    assert_eq!(transcript::replay(&code, &events).unwrap(), None);
+}
+
+#[test]
+fn replay_reports_where_outputs_diverge() {
+    let code = intcode::parse_code(DOUBLER).unwrap();
+    let changed = [
+        Event::Input(3),
+        Event::Output(6),
+        Event::Input(-7),
+        Event::Output(14),
+    ];
+    let divergence = transcript::replay(&code, &changed).unwrap().unwrap();
+    assert_eq!(
+        divergence,
+        Divergence {
+            index: 3,
+            expected: Some(Event::Output(14)),
+            actual: Some(Event::Output(-14)),
+        }
+    );
+    assert_eq!(
+        divergence.to_string(),
+        "event 4: expected out 14, got out -14"
+    );
+
+    // The program halts after two outputs, so a third never comes.
+    let missing = [
+        Event::Input(3),
+        Event::Output(6),
+        Event::Input(-7),
+        Event::Output(-14),
+        Event::Output(0),
+    ];
+    assert_eq!(
+        transcript::replay(&code, &missing).unwrap(),
+        Some(Divergence {
+            index: 4,
+            expected: Some(Event::Output(0)),
+            actual: None,
+        })
+    );
+
+    // Output the transcript does not mention is reported after its last event.
+    let truncated = [Event::Input(3), Event::Output(6), Event::Input(-7)];
+    assert_eq!(
+        transcript::replay(&code, &truncated).unwrap(),
+        Some(Divergence {
+            index: truncated.len(),
+            expected: None,
+            actual: Some(Event::Output(-14)),
+        })
+    );
+}
+
+#[test]
+fn malformed_transcripts_report_the_line() {
+    let err = transcript::parse_transcript("in 1\n\nout 2\nout two\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 4: expected \"in N\" or \"out N\", found \"out two\""
+    );
+    for line in ["in", "in 1 2", "put 1"] {
+        assert!(transcript::parse_transcript(line).is_err(), "{line}");
+    }
+}
+
+#[test]
+fn recorder_keeps_output_from_before_a_fault() {
+    // Prints 5, then jumps past the halt to a cell holding no valid opcode.
+    let mut recorder = Recorder::new(&intcode::parse_code("104,5,1105,1,9,99,0,0,0,0").unwrap());
+    assert!(recorder.run_adv().is_err());
+    assert_eq!(recorder.events(), &[Event::Output(5)]);
+}
+
+#[test]
+fn aot_guards_instructions_when_writes_are_unknown() {
+    let quine = aot::Program::analyse(&intcode::parse_code(QUINE).unwrap());
+    assert!(quine.is_static());
+    assert_eq!(quine.compiled_count(), 6);
+
+    // Relative writes may land anywhere, but only the cells they hit fall back.
+    let self_test =
+        aot::Program::analyse(&intcode::parse_code(&common::example(SELF_TEST)).unwrap());
+    assert!(!self_test.is_static());
+    assert_eq!(self_test.compiled_count(), 26);
+
+    // The jump in relative mode leads to code that rewrites the output at 5.
+    let code = "109,10,2105,1,0,104,7,99,0,0,11,1101,0,42,6,1105,1,5";
+    let program = aot::Program::analyse(&intcode::parse_code(code).unwrap());
+    assert!(!program.is_static());
+    assert!(program.emit().contains("5 if self.unchanged(&[5, 6]) => {"));
+}
+
+// Builds the module intcode-aot emits into a binary that prints the output for the input
+// given as arguments, and checks that it behaves like the interpreter.
+fn assert_aot_matches_interpreter(name: &str, program: &str, inputs: &[Vec<i64>]) {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("aot-{name}"));
+    fs::create_dir_all(&dir).unwrap();
+    let emitted = aot::Program::analyse(&intcode::parse_code(program).unwrap()).emit();
+    fs::write(dir.join("program.rs"), emitted).unwrap();
+    fs::write(
+        dir.join("main.rs"),
+        "mod program;
+
+fn main() {
+    let input = std::env::args().skip(1).map(|a| a.parse().unwrap()).collect();
+    let mut computer = program::Computer::new(input);
+    computer.run().unwrap();
+    println!(\"{:?}\", computer.output());
+}
+",
+    )
+    .unwrap();
+    let binary = dir.join("program");
+    let status = Command::new("rustc")
+        .args(["--edition", "2021", "-A", "warnings", "-o"])
+        .arg(&binary)
+        .arg(dir.join("main.rs"))
+        .status()
+        .unwrap();
+    assert!(
+        status.success(),
+        "{name}: generated module does not compile"
+    );
+
+    for input in inputs {
+        let output = Command::new(&binary)
+            .args(input.iter().map(|i| i.to_string()))
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap().trim_end(),
+            format!("{:?}", run(program, input.clone())),
+            "{name} with input {input:?}"
+        );
+    }
+}
+
+#[test]
+fn aot_build_matches_interpreter() {
+    assert_aot_matches_interpreter("quine", QUINE, &[vec![]]);
+    assert_aot_matches_interpreter(
+        "compare",
+        &common::example(COMPARE),
+        &[vec![7], vec![8], vec![9]],
+    );
+    assert_aot_matches_interpreter(
+        "self-test",
+        &common::example(SELF_TEST),
+        &[vec![1], vec![2]],
+    );
+    assert_aot_matches_interpreter(
+        "relative-jump",
+        "109,10,2105,1,0,104,7,99,0,0,11,1101,0,42,6,1105,1,5",
+        &[vec![]],
+    );
+}
+
+#[test]
+fn intcode_binary_exits_with_the_halt_reason() {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let run = |program: &str, args: &[&str]| {
+        let path = dir.join("intcode-binary.txt");
+        fs::write(&path, program).unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_intcode"))
+            .arg(&path)
+            .args(args)
+            .output()
+            .unwrap();
+        (
+            output.status.code().unwrap(),
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+
+    let (code, stdout, _) = run(
+        &common::example(COMPARE),
+        &["--input", "9", "--format", "csv"],
+    );
+    assert_eq!((code, stdout.as_str()), (0, "1001\n"));
+    // Patches turn the add into an output followed by a halt.
+    let (code, stdout, _) = run("1,7,1,0,99", &["--set", "0=104", "--set", "2=99"]);
+    assert_eq!((code, stdout.as_str()), (0, "7\n"));
+    assert_eq!(run("3,0,99", &[]).0, 2);
+    assert_eq!(run("104,1,0", &[]).0, 3);
+    let (code, stdout, _) = run("104,1,1105,1,0", &["--steps", "5"]);
+    assert_eq!((code, stdout.as_str()), (4, "1\n1\n1\n"));
+
+    let (code, _, stderr) = run("99", &["--steps", "many"]);
+    assert_eq!(code, 1);
+    assert!(stderr.starts_with("error: --steps expects a count, found \"many\"\n"));
+    let (code, _, stderr) = run("99", &["--set", "5=1"]);
+    assert_eq!(code, 1);
+    assert!(stderr.starts_with("Address 5 is outside of the program\n"));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use aoc19::math::{checked_lcm, gcd, lcm};
+use aoc19::moons::{self, Moon};
+
+fn example(name: &str) -> Vec<Moon> {
+    moons::parse_moons(&common::example(name)).unwrap()
+}
+
+fn moon(position: [i64; 3], velocity: [i64; 3]) -> Moon {
+    Moon { position, velocity }
+}
+
+#[test]
+fn parses_positions_at_rest() {
+    assert_eq!(
+        moons::parse_moon("<x=-1, y=0, z=2>").unwrap(),
+        moon([-1, 0, 2], [0, 0, 0])
+    );
+    assert!(moons::parse_moon("<x=1, y=2>").is_err());
+    assert!(moons::parse_moon("<x=1, z=2, y=3>").is_err());
+    assert!(moons::parse_moon("<x=1, y=2, z=3, w=4>").is_err());
+    assert!(moons::parse_moon("x=1, y=2, z=3").is_err());
+    assert!(moons::parse_moon("<x=1, y=two, z=3>").is_err());
+}
+
+#[test]
+fn steps_gravity_then_velocity() {
+    let mut moons = example("day12-1.txt");
+    moons::step(&mut moons);
+    assert_eq!(
+        moons,
+        vec![
+            moon([2, -1, 1], [3, -1, -1]),
+            moon([3, -7, -4], [1, 3, 3]),
+            moon([1, -7, 5], [-3, 1, -3]),
+            moon([2, 2, 0], [-1, -3, 1]),
+        ]
+    );
+}
+
+#[test]
+fn computes_total_energy() {
+    for (name, steps, energy) in [("day12-1.txt", 10, 179), ("day12-2.txt", 100, 1940)] {
+        let mut moons = example(name);
+        for _ in 0..steps {
+            moons::step(&mut moons);
+        }
+        assert_eq!(moons::total_energy(&moons), energy, "{name}");
+    }
+}
+
+#[test]
+fn finds_repeat_period() {
+    assert_eq!(moons::period(&example("day12-1.txt")).unwrap(), 2772);
+    assert_eq!(moons::period(&example("day12-2.txt")).unwrap(), 4686774924);
+}
+
+#[test]
+fn gcd_and_lcm_are_never_negative() {
+    assert_eq!(gcd(12, 18), 6);
+    assert_eq!(gcd(-12, 18), 6);
+    assert_eq!(gcd(0, -5), 5);
+    assert_eq!(gcd(0_u64, 0), 0);
+    assert_eq!(lcm(4_u64, 6), 12);
+    assert_eq!(lcm(-4, 6), 12);
+    assert_eq!(lcm(0, 6), 0);
+    assert_eq!(lcm(18_u64, lcm(28, 44)), 2772);
+}
+
+#[test]
+fn checked_lcm_reports_overflow() {
+    assert_eq!(checked_lcm(4, 6), Some(12));
+    assert_eq!(checked_lcm(0, u64::MAX), Some(0));
+    assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+    assert_eq!(checked_lcm(1 << 32, (1 << 32) + 1), None);
+    assert_eq!(checked_lcm(1 << 32, 3 << 31), Some(3 << 32));
+}
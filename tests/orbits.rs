@@ -0,0 +1,260 @@
+mod common;
+
+use aoc19::error::Error;
+use aoc19::orbits::{viz, Diagnostic, OrbitError, OrbitMap, OrbitQueries};
+
+const EXAMPLE: &str = "day06-1.txt";
+
+// The first example with YOU orbiting K and SAN orbiting I.
+const TRANSFER: &str = "day06-2.txt";
+
+fn example(name: &str) -> OrbitMap {
+    OrbitMap::parse(&common::example(name)).unwrap()
+}
+
+fn orbit_error(s: &str) -> OrbitError {
+    let mut e = OrbitMap::parse(s).err().unwrap();
+    loop {
+        match e {
+            Error::Context { source, .. } => e = *source,
+            Error::Orbit(e) => return e,
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+}
+
+#[test]
+fn parse_rejects_malformed_lines() {
+    for line in ["COM", "COM)", ")B", "A)B)C", "A-B"] {
+        assert_eq!(
+            orbit_error(&format!("COM)A\n{line}\n")),
+            OrbitError::Malformed(String::from(line))
+        );
+    }
+    assert_eq!(
+        OrbitMap::parse("COM)A\n\nA)B)C").err().unwrap().to_string(),
+        "line 3: malformed orbit \"A)B)C\""
+    );
+}
+
+#[test]
+fn parse_rejects_bodies_with_two_parents() {
+    assert_eq!(
+        orbit_error("COM)A\nCOM)B\nA)C\nB)C"),
+        OrbitError::DuplicateChild(String::from("C"))
+    );
+    assert_eq!(
+        OrbitMap::parse("COM)A\nCOM)B\nA)C\nB)C")
+            .err()
+            .unwrap()
+            .to_string(),
+        "line 4: C orbits more than one body"
+    );
+}
+
+#[test]
+fn depths_count_direct_and_indirect_orbits() {
+    let m = example(EXAMPLE);
+    let depths = m.depths();
+    let depth = |name: &str| depths[m.id(name).unwrap()];
+    assert_eq!(depth("COM"), 0);
+    assert_eq!(depth("D"), 3);
+    assert_eq!(depth("L"), 7);
+    assert_eq!(m.total_orbits(), 42);
+    assert_eq!(m.name(m.root()), "COM");
+    assert_eq!(m.len(), 12);
+}
+
+#[test]
+fn total_orbits_of_a_long_chain() {
+    // Deep enough to overflow the stack if depths were found recursively.
+    let mut chain = String::from("COM)N0\n");
+    for i in 1..100_000 {
+        chain += &format!("N{})N{i}\n", i - 1);
+    }
+    let m = OrbitMap::parse(&chain).unwrap();
+    assert_eq!(m.total_orbits(), 100_000 * 100_001 / 2);
+}
+
+#[test]
+fn empty_map_has_no_orbits() {
+    let m = OrbitMap::parse("").unwrap();
+    assert!(m.is_empty());
+    assert_eq!(m.total_orbits(), 0);
+}
+
+#[test]
+fn queries_find_common_ancestors_and_distances() {
+    let m = example(TRANSFER);
+    let queries = OrbitQueries::new(&m);
+    assert_eq!(queries.lca("YOU", "SAN").unwrap(), "D");
+    assert_eq!(queries.lca("L", "H").unwrap(), "B");
+    assert_eq!(queries.lca("E", "L").unwrap(), "E");
+    assert_eq!(queries.lca("COM", "COM").unwrap(), "COM");
+    assert_eq!(queries.distance("YOU", "SAN").unwrap(), 6);
+    assert_eq!(queries.distance("L", "COM").unwrap(), 7);
+    assert_eq!(queries.distance("F", "F").unwrap(), 0);
+    assert_eq!(
+        queries.lca("YOU", "ZZZ"),
+        Err(OrbitError::UnknownBody(String::from("ZZZ")))
+    );
+}
+
+#[test]
+fn queries_agree_with_walking_up_the_tree() {
+    let m = example(TRANSFER);
+    let queries = OrbitQueries::new(&m);
+    let names = (0..m.len()).map(|id| m.name(id)).collect::<Vec<&str>>();
+    for &a in &names {
+        for &b in &names {
+            let mut above_a = m.ancestors(m.id(a).unwrap());
+            above_a.push(m.id(a).unwrap());
+            let mut above_b = m.ancestors(m.id(b).unwrap());
+            above_b.push(m.id(b).unwrap());
+            let shared = above_a
+                .iter()
+                .zip(&above_b)
+                .take_while(|(x, y)| x == y)
+                .count();
+            let expected = above_a.len() + above_b.len() - 2 * shared;
+            assert_eq!(queries.lca(a, b).unwrap(), m.name(above_a[shared - 1]));
+            assert_eq!(queries.distance(a, b).unwrap(), expected, "{a} to {b}");
+            assert_eq!(
+                queries.path(a, b).unwrap().len(),
+                expected + 1,
+                "{a} to {b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn paths_run_through_the_common_ancestor() {
+    let m = example(TRANSFER);
+    let queries = OrbitQueries::new(&m);
+    assert_eq!(
+        queries.path("YOU", "SAN").unwrap(),
+        vec!["YOU", "K", "J", "E", "D", "I", "SAN"]
+    );
+    assert_eq!(queries.path("C", "C").unwrap(), vec!["C"]);
+    assert_eq!(queries.path("COM", "C").unwrap(), vec!["COM", "B", "C"]);
+}
+
+#[test]
+fn transfers_move_between_the_orbited_bodies() {
+    let m = example(TRANSFER);
+    let queries = OrbitQueries::new(&m);
+    assert_eq!(queries.transfers("YOU", "SAN").unwrap(), 4);
+    assert_eq!(queries.transfers("YOU", "L").unwrap(), 0);
+    assert_eq!(queries.transfers("YOU", "K").unwrap(), 1);
+    assert_eq!(
+        queries.transfers("COM", "SAN"),
+        Err(OrbitError::NotOrbiting(String::from("COM")))
+    );
+    assert_eq!(
+        queries.transfers("YOU", "X"),
+        Err(OrbitError::UnknownBody(String::from("X")))
+    );
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|&name| String::from(name)).collect()
+}
+
+#[test]
+fn diagnose_reports_cycles_roots_and_unreachable_bodies() {
+    // X is a second root, C and D orbit each other and E hangs off the cycle.
+    let map = "COM)A\nA)B\nX)Y\nC)D\nD)C\nD)E\n";
+    assert_eq!(
+        orbit_error(map),
+        OrbitError::Invalid(Diagnostic {
+            missing_root: None,
+            extra_roots: names(&["X"]),
+            cycles: names(&["C", "D"]),
+            unreachable: names(&["E", "Y"]),
+        })
+    );
+    assert_eq!(
+        OrbitMap::parse(map).err().unwrap().to_string(),
+        "invalid orbit map: other roots: X; bodies in cycles: C, D; unreachable bodies: E, Y"
+    );
+}
+
+#[test]
+fn diagnose_reports_a_missing_or_orbiting_root() {
+    assert_eq!(
+        orbit_error("A)B"),
+        OrbitError::Invalid(Diagnostic {
+            missing_root: Some(String::from("COM")),
+            extra_roots: names(&["A"]),
+            cycles: vec![],
+            unreachable: names(&["B"]),
+        })
+    );
+    // The root orbiting something makes that the root of a tree instead.
+    assert_eq!(
+        orbit_error("A)COM\nCOM)B"),
+        OrbitError::Invalid(Diagnostic {
+            missing_root: Some(String::from("COM")),
+            extra_roots: names(&["A"]),
+            cycles: vec![],
+            unreachable: names(&["B", "COM"]),
+        })
+    );
+    assert!(OrbitMap::parse_with_root("A)B\nB)C", "A").is_ok());
+}
+
+#[test]
+fn diagnose_finds_a_cycle_through_the_root() {
+    assert_eq!(
+        orbit_error("COM)A\nA)COM"),
+        OrbitError::Invalid(Diagnostic {
+            missing_root: Some(String::from("COM")),
+            extra_roots: vec![],
+            cycles: names(&["A", "COM"]),
+            unreachable: vec![],
+        })
+    );
+}
+
+const SMALL: &str = "COM)B\nB)D\nB)C\nD)E\n";
+
+#[test]
+fn tree_lists_children_by_name_with_subtree_sizes() {
+    let m = OrbitMap::parse(SMALL).unwrap();
+    assert_eq!(
+        viz::to_tree(&m),
+        "COM (4)\n  B (3)\n    C (0)\n    D (1)\n      E (0)\n"
+    );
+    assert_eq!(viz::to_tree(&OrbitMap::parse("").unwrap()), "");
+}
+
+#[test]
+fn dot_draws_edges_and_highlights_a_path() {
+    let m = OrbitMap::parse(SMALL).unwrap();
+    assert_eq!(
+        viz::to_dot(&m, None).unwrap(),
+        "digraph orbits {\n    \"COM\" -> \"B\";\n    \"B\" -> \"D\";\n    \"B\" -> \"C\";\n    \"D\" -> \"E\";\n}\n"
+    );
+
+    let highlighted = viz::to_dot(&m, Some(("C", "E"))).unwrap();
+    for name in ["B", "C", "D", "E"] {
+        assert!(highlighted.contains(&format!("    \"{name}\" [style=filled, fillcolor=red];\n")));
+    }
+    assert!(!highlighted.contains("\"COM\" [style"));
+    assert!(highlighted.contains("    \"COM\" -> \"B\";\n"));
+    assert!(highlighted.contains("    \"B\" -> \"C\" [color=red, penwidth=2];\n"));
+    assert!(highlighted.contains("    \"D\" -> \"E\" [color=red, penwidth=2];\n"));
+
+    assert_eq!(
+        viz::to_dot(&m, Some(("C", "Q"))),
+        Err(OrbitError::UnknownBody(String::from("Q")))
+    );
+}
+
+#[test]
+fn query_results_outlive_the_queries() {
+    let m = OrbitMap::parse(SMALL).unwrap();
+    let path = OrbitQueries::new(&m).path("C", "E").unwrap();
+    assert_eq!(path, vec!["C", "B", "D", "E"]);
+}
@@ -0,0 +1,260 @@
+use aoc19::error::Error;
+use aoc19::password::{
+    self, OrderRule, ParseRangeError, Rules, DOUBLE_DIGITS, EXACT_DOUBLE_DIGITS, NONDECREASING,
+};
+use std::ops::RangeInclusive;
+use std::process::Command;
+
+const NONINCREASING: OrderRule = OrderRule {
+    name: "digits_form_nonincreasing_sequence",
+    predicate: |prev, next| prev >= next,
+};
+
+const INCREASING: OrderRule = OrderRule {
+    name: "digits_form_increasing_sequence",
+    predicate: |prev, next| prev < next,
+};
+
+fn rule_sets() -> Vec<Rules> {
+    vec![
+        Rules::part1(),
+        Rules::part2(),
+        Rules {
+            order: None,
+            runs: vec![DOUBLE_DIGITS],
+        },
+        Rules {
+            order: Some(NONINCREASING),
+            runs: vec![EXACT_DOUBLE_DIGITS],
+        },
+        Rules {
+            order: Some(INCREASING),
+            runs: vec![],
+        },
+    ]
+}
+
+// Checks a number the slow way, on its decimal string.
+fn brute_force(number: u64, rules: &Rules) -> bool {
+    let digits = number
+        .to_string()
+        .bytes()
+        .map(|b| b - b'0')
+        .collect::<Vec<u8>>();
+    let mut runs = Vec::<u32>::new();
+    for (i, d) in digits.iter().enumerate() {
+        if i > 0 && digits[i - 1] == *d {
+            *runs.last_mut().unwrap() += 1;
+        } else {
+            runs.push(1);
+        }
+    }
+    rules
+        .order
+        .is_none_or(|order| digits.windows(2).all(|w| (order.predicate)(w[0], w[1])))
+        && rules
+            .runs
+            .iter()
+            .all(|rule| runs.iter().any(|&run| (rule.predicate)(run)))
+}
+
+fn parse_range(s: &str, strict: bool) -> Result<RangeInclusive<u64>, ParseRangeError> {
+    password::parse_range(s, strict).map_err(|e| match e.cause() {
+        Error::Range(e) => *e,
+        e => panic!("unexpected error: {e}"),
+    })
+}
+
+#[test]
+fn parse_range_includes_both_bounds() {
+    assert_eq!(parse_range("108457-562041", true), Ok(108457..=562041));
+    assert_eq!(parse_range("111111-111111\n", true), Ok(111111..=111111));
+    assert_eq!(parse_range("100000-999999", true), Ok(100000..=999999));
+}
+
+#[test]
+fn parse_range_rejects_reversed_bounds() {
+    assert_eq!(
+        parse_range("562041-108457", true),
+        Err(ParseRangeError::Reversed)
+    );
+    assert_eq!(parse_range("11-10", false), Err(ParseRangeError::Reversed));
+}
+
+#[test]
+fn parse_range_rejects_extra_segments() {
+    assert_eq!(parse_range("1-2-3", false), Err(ParseRangeError::Malformed));
+    assert_eq!(
+        parse_range("108457-562041-", true),
+        Err(ParseRangeError::Malformed)
+    );
+    assert_eq!(
+        parse_range("-108457", true),
+        Err(ParseRangeError::Malformed)
+    );
+    assert_eq!(parse_range("108457", true), Err(ParseRangeError::Malformed));
+    assert_eq!(parse_range("+1-2", false), Err(ParseRangeError::Malformed));
+}
+
+#[test]
+fn parse_range_strict_mode_requires_six_digits() {
+    assert_eq!(
+        parse_range("99999-562041", true),
+        Err(ParseRangeError::NotSixDigits)
+    );
+    assert_eq!(
+        parse_range("108457-1000000", true),
+        Err(ParseRangeError::NotSixDigits)
+    );
+    assert_eq!(
+        parse_range("012345-562041", true),
+        Err(ParseRangeError::NotSixDigits)
+    );
+    assert_eq!(parse_range("99999-1000000", false), Ok(99999..=1000000));
+}
+
+#[test]
+fn count_includes_upper_bound() {
+    assert_eq!(password::count(111110..=111111, &Rules::part1()), 1);
+    assert_eq!(password::count(111111..=111111, &Rules::part1()), 1);
+    assert_eq!(password::count(111111..=111111, &Rules::part2()), 0);
+    assert_eq!(password::count(112233..=112233, &Rules::part2()), 1);
+    assert_eq!(password::count(999999..=999999, &Rules::part1()), 1);
+}
+
+#[test]
+fn count_matches_brute_force() {
+    const LIMIT: u64 = 130_000;
+    for rules in rule_sets() {
+        // Valid numbers below each bound.
+        let mut below = vec![0_u64];
+        for number in 0..=LIMIT {
+            below.push(below[number as usize] + brute_force(number, &rules) as u64);
+        }
+        for lo in [0, 1, 9, 10, 99, 110, 1234, 11111, 99999, 100000, 123455] {
+            for hi in [0, 8, 11, 100, 1000, 22222, 99999, 111111, 122333, LIMIT] {
+                let expected = below[hi as usize + 1].saturating_sub(below[lo as usize]);
+                assert_eq!(password::count(lo..=hi, &rules), expected, "{lo}..={hi}");
+            }
+        }
+    }
+}
+
+#[test]
+fn order_is_configurable() {
+    let descending = Rules {
+        order: Some(NONINCREASING),
+        runs: vec![DOUBLE_DIGITS],
+    };
+    assert!(descending.is_valid(998765));
+    assert!(!descending.is_valid(112233));
+    assert!(Rules::part1().is_valid(112233));
+    assert_eq!(password::count(100000..=999999, &descending), 4794);
+}
+
+#[test]
+fn candidates_match_brute_force() {
+    for rules in rule_sets() {
+        for (lo, hi) in [(0, 30_000), (99_990, 130_000), (123_456, 123_456)] {
+            let expected = (lo..=hi)
+                .filter(|&n| brute_force(n, &rules))
+                .collect::<Vec<u64>>();
+            assert_eq!(
+                password::candidates(lo..=hi, &rules).collect::<Vec<u64>>(),
+                expected,
+                "{lo}..={hi}"
+            );
+        }
+    }
+    let rules = Rules::part1();
+    assert_eq!(password::candidates(u64::MAX - 1000.., &rules).count(), 0);
+}
+
+#[test]
+fn explain_names_each_failed_rule() {
+    for rules in rule_sets() {
+        for number in 0..=30_000 {
+            let failed = password::explain(number, &rules);
+            assert_eq!(failed.is_empty(), brute_force(number, &rules), "{number}");
+        }
+    }
+    assert_eq!(
+        password::explain(123444, &Rules::part2()),
+        vec!["has_exact_double_digits_at_least_once"]
+    );
+    assert_eq!(
+        password::explain(223450, &Rules::part1()),
+        vec!["digits_form_nondecreasing_sequence"]
+    );
+    assert_eq!(
+        password::explain(123789, &Rules::part1()),
+        vec!["has_double_digits_at_least_once"]
+    );
+    assert_eq!(
+        password::explain(987654, &Rules::part1()),
+        vec![
+            "has_double_digits_at_least_once",
+            "digits_form_nondecreasing_sequence"
+        ]
+    );
+}
+
+#[test]
+fn next_from_matches_brute_force() {
+    const LIMIT: u64 = 200_000;
+    for order in [NONDECREASING, NONINCREASING, INCREASING] {
+        let ordered = |n: u64| {
+            let digits = n.to_string().into_bytes();
+            digits.windows(2).all(|w| (order.predicate)(w[0], w[1]))
+        };
+        // The next ordered number, scanning down from one known to be ordered.
+        let mut next = (LIMIT..).find(|&n| ordered(n)).unwrap();
+        for number in (0..LIMIT).rev() {
+            if ordered(number) {
+                next = number;
+            }
+            assert_eq!(
+                order.next_from(number),
+                Some(next),
+                "{}: {number}",
+                order.name
+            );
+        }
+    }
+    assert_eq!(NONDECREASING.next_from(u64::MAX - 5), None);
+    assert_eq!(INCREASING.next_from(123456790), None);
+    assert_eq!(NONINCREASING.next_from(19), Some(20));
+}
+
+#[test]
+fn password_check_lists_and_explains() {
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_password-check"))
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{args:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    for (part, rules) in [("1", Rules::part1()), ("2", Rules::part2())] {
+        let expected = (111_000..=124_000)
+            .filter(|&n| brute_force(n, &rules))
+            .map(|n| format!("{n}\n"))
+            .collect::<String>();
+        assert_eq!(run(&["--part", part, "111000-124000"]), expected);
+    }
+    assert_eq!(
+        run(&["112233", "123444", "223450"]),
+        "112233: valid\n\
+         123444: fails has_exact_double_digits_at_least_once\n\
+         223450: fails digits_form_nondecreasing_sequence\n"
+    );
+
+    let status = Command::new(env!("CARGO_BIN_EXE_password-check"))
+        .arg("9-1")
+        .output()
+        .unwrap()
+        .status;
+    assert!(!status.success());
+}
@@ -0,0 +1,383 @@
+use aoc19::wires::{self, svg, Direction, Overlap, Point, Retrace, Wire};
+use std::collections::{BTreeMap, BTreeSet};
+
+fn wire(motions: &str) -> Wire {
+    motions.parse().unwrap()
+}
+
+#[test]
+fn parses_motions_with_columns() {
+    let motions = wires::parse_motions("R8,U5,L5,D3").unwrap();
+    assert_eq!(motions.len(), 4);
+    assert_eq!(motions[1].direction, Direction::Up);
+    assert_eq!(motions[1].dist, 5);
+    assert!(wires::parse_motion("X3").is_err());
+    assert!(wires::parse_motion("U-3").is_err());
+    assert_eq!(
+        wires::parse_motions("R8,U5,Q5").unwrap_err().to_string(),
+        "column 7: expected a direction U, D, L or R and a distance, found \"Q5\""
+    );
+}
+
+#[test]
+fn follows_motions_from_the_origin() {
+    let w = wire("R8,U5,L5,D3");
+    let ends = w.segments().iter().map(|s| s.end()).collect::<Vec<Point>>();
+    assert_eq!(
+        ends,
+        vec![Point(8, 0), Point(8, 5), Point(3, 5), Point(3, 2)]
+    );
+    assert_eq!(w.segments()[2].steps, 13);
+}
+
+fn points(found: &[wires::Intersection]) -> Vec<Point> {
+    found.iter().map(|i| i.point).collect()
+}
+
+#[test]
+fn finds_the_example_intersections() {
+    let (fst, snd) = (wire("R8,U5,L5,D3"), wire("U7,R6,D4,L4"));
+    let found = wires::intersections(&fst, &snd);
+    assert_eq!(points(&found.points), vec![Point(3, 3), Point(6, 5)]);
+    assert!(found.overlaps.is_empty());
+    assert_eq!(found.points[0].distance, 6);
+    assert_eq!(found.points[0].steps, 40);
+    assert_eq!(found.points[0].arrivals, vec![(0, 20), (1, 20)]);
+    assert_eq!(found.points[1].steps, 30);
+    assert_eq!(found.closest.unwrap().point, Point(3, 3));
+    assert_eq!(found.fewest_steps.unwrap().point, Point(6, 5));
+}
+
+#[test]
+fn wires_that_do_not_meet_have_no_intersections() {
+    let found = wires::intersections(&wire("R5,U5"), &wire("L5,D5"));
+    assert!(found.points.is_empty() && found.overlaps.is_empty());
+    assert_eq!(found.closest, None);
+    assert_eq!(found.fewest_steps, None);
+}
+
+#[test]
+fn collinear_overlaps_are_reported_as_stretches() {
+    // The second wire joins the first at x=10 and runs along it to the end.
+    let (fst, snd) = (wire("R1000000"), wire("U5,R10,D5,R999990"));
+    let found = wires::intersections(&fst, &snd);
+    assert!(found.points.is_empty());
+    assert_eq!(
+        found.overlaps,
+        vec![Overlap {
+            from: Point(10, 0),
+            to: Point(1000000, 0),
+            wires: vec![0, 1],
+        }]
+    );
+    let closest = found.closest.unwrap();
+    assert_eq!((closest.point, closest.steps), (Point(10, 0), 30));
+    // Along the overlap both wires take a step for every unit, so the start is cheapest.
+    assert_eq!(found.fewest_steps.unwrap().point, Point(10, 0));
+}
+
+#[test]
+fn overlaps_through_the_origin_are_searched_beside_it() {
+    // Running back along the first wire, the second meets it next to the origin.
+    let (fst, snd) = (wire("R10"), wire("U1,R10,D1,L10"));
+    let found = wires::intersections(&fst, &snd);
+    assert!(found.points.is_empty());
+    assert_eq!(
+        found.overlaps,
+        vec![Overlap {
+            from: Point(0, 0),
+            to: Point(10, 0),
+            wires: vec![0, 1],
+        }]
+    );
+    let closest = found.closest.clone().unwrap();
+    assert_eq!(closest.point, Point(1, 0));
+    assert_eq!(closest.arrivals, vec![(0, 1), (1, 21)]);
+    // The far end takes as many steps in total, but is further away.
+    assert_eq!(found.fewest_steps.unwrap(), closest);
+}
+
+#[test]
+fn crossings_of_at_least_k_wires() {
+    // The last two wires overlap along x=5, where the first crosses them both.
+    let wires = [wire("R10"), wire("U5,R5,D10"), wire("D5,R5,U10")];
+    let wires = wires.iter().collect::<Vec<&Wire>>();
+    let found = wires::crossings(&wires, 3);
+    assert!(found.overlaps.is_empty());
+    assert_eq!(points(&found.points), vec![Point(5, 0)]);
+    assert_eq!(found.points[0].arrivals, vec![(0, 5), (1, 15), (2, 15)]);
+    assert_eq!(found.points[0].steps, 35);
+
+    let pairs = wires::crossings(&wires, 2);
+    assert_eq!(
+        pairs.overlaps,
+        vec![Overlap {
+            from: Point(5, -5),
+            to: Point(5, 5),
+            wires: vec![1, 2],
+        }]
+    );
+    // The point where all three meet is kept apart from the overlap of two.
+    assert_eq!(pairs.points, found.points);
+    assert_eq!(pairs.closest.as_ref().unwrap().point, Point(5, 0));
+    // Along the overlap the two wires take 30 steps between them, but the first adds to that
+    // where it crosses, so the fewest are beside it.
+    let fewest = pairs.fewest_steps.as_ref().unwrap();
+    assert_eq!((fewest.point, fewest.steps), (Point(5, -1), 30));
+    // Fewer than two wires do not make a crossing.
+    assert_eq!(wires::crossings(&wires, 0), pairs);
+}
+
+#[test]
+fn overlaps_split_where_a_wire_joins() {
+    let wires = [wire("R10"), wire("R6"), wire("U1,R3,D1,R5")];
+    let wires = wires.iter().collect::<Vec<&Wire>>();
+    let found = wires::crossings(&wires, 2);
+    let stretches = found
+        .overlaps
+        .iter()
+        .map(|o| (o.from, o.to, o.wires.clone()))
+        .collect::<Vec<(Point, Point, Vec<usize>)>>();
+    assert_eq!(
+        stretches,
+        vec![
+            (Point(0, 0), Point(2, 0), vec![0, 1]),
+            (Point(3, 0), Point(6, 0), vec![0, 1, 2]),
+            (Point(7, 0), Point(8, 0), vec![0, 2]),
+        ]
+    );
+    assert!(found.points.is_empty());
+    assert_eq!(found.closest.unwrap().point, Point(1, 0));
+    assert_eq!(wires::crossings(&wires, 3).overlaps.len(), 1);
+}
+
+#[test]
+fn self_crossings_list_every_visit() {
+    // Crosses its first segment on the way down.
+    let revisits = wires::self_crossings(&wire("R5,U2,L2,D4"));
+    assert!(revisits.retraces.is_empty());
+    assert_eq!(points_of(&revisits.points), vec![Point(3, 0)]);
+    assert_eq!(revisits.points[0].visits, vec![3, 11]);
+
+    // Doubling back retraces a stretch; the joint where it turns is not a revisit.
+    let revisits = wires::self_crossings(&wire("R5,L2"));
+    assert!(revisits.points.is_empty());
+    assert_eq!(
+        revisits.retraces,
+        vec![Retrace {
+            from: Point(3, 0),
+            to: Point(4, 0),
+            times: 2,
+        }]
+    );
+
+    // Looping back to the origin and setting off along the first segment again.
+    let revisits = wires::self_crossings(&wire("R5,U1,L5,D1,R2,D1,R5,U1"));
+    assert_eq!(points_of(&revisits.points), vec![Point(0, 0)]);
+    assert_eq!(revisits.points[0].visits, vec![0, 12]);
+    assert_eq!(
+        revisits.retraces,
+        vec![Retrace {
+            from: Point(1, 0),
+            to: Point(2, 0),
+            times: 2,
+        }]
+    );
+
+    let revisits = wires::self_crossings(&wire("R5,U2,L2"));
+    assert!(revisits.points.is_empty() && revisits.retraces.is_empty());
+}
+
+fn points_of(revisits: &[wires::Revisit]) -> Vec<Point> {
+    revisits.iter().map(|r| r.point).collect()
+}
+
+#[test]
+fn steps_count_the_first_arrival_of_each_wire() {
+    let fst = wire("R5,U2,L2,D4");
+    assert_eq!(fst.first_arrival(Point(3, 0)), Some(3));
+    assert_eq!(fst.first_arrival(Point(3, 1)), Some(10));
+    assert_eq!(fst.first_arrival(Point(1, 1)), None);
+
+    // The second wire meets the first where it passes twice, then runs along it.
+    let found = wires::intersections(&fst, &wire("D1,R3,U1"));
+    assert_eq!(
+        found.overlaps,
+        vec![Overlap {
+            from: Point(3, -1),
+            to: Point(3, 0),
+            wires: vec![0, 1],
+        }]
+    );
+    let fewest = found.fewest_steps.unwrap();
+    assert_eq!(fewest.point, Point(3, 0));
+    assert_eq!(fewest.arrivals, vec![(0, 3), (1, 5)]);
+    assert_eq!(fewest.steps, 8);
+}
+
+// Every point each wire visits, with the steps of every visit.
+fn trace(w: &Wire) -> BTreeMap<Point, BTreeSet<i64>> {
+    let mut visits = BTreeMap::<Point, BTreeSet<i64>>::new();
+    for segment in w.segments() {
+        let end = segment.end();
+        let (dx, dy) = (
+            (end.0 - segment.start.0).signum(),
+            (end.1 - segment.start.1).signum(),
+        );
+        for k in 0..=segment.length {
+            let p = Point(segment.start.0 + k * dx, segment.start.1 + k * dy);
+            visits
+                .entry(p)
+                .or_default()
+                .insert(segment.steps + k as i64);
+        }
+    }
+    visits
+}
+
+fn stretch(from: Point, to: Point) -> Vec<Point> {
+    (from.0..=to.0)
+        .flat_map(|x| (from.1..=to.1).map(move |y| Point(x, y)))
+        .collect()
+}
+
+fn random_wire(seed: &mut u64) -> Wire {
+    let mut next = |n: u64| {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (*seed >> 33) % n
+    };
+    let motions = (0..6)
+        .map(|_| format!("{}{}", ["U", "D", "L", "R"][next(4) as usize], next(5)))
+        .collect::<Vec<String>>();
+    wire(&motions.join(","))
+}
+
+#[test]
+fn crossings_agree_with_tracing_every_point() {
+    let mut seed = 7;
+    for _ in 0..300 {
+        let wires = (0..3)
+            .map(|_| random_wire(&mut seed))
+            .collect::<Vec<Wire>>();
+        let traces = wires.iter().map(trace).collect::<Vec<_>>();
+        let wires = wires.iter().collect::<Vec<&Wire>>();
+        for min_wires in 2..=3 {
+            let mut expected = BTreeMap::<Point, Vec<(usize, i64)>>::new();
+            for (w, t) in traces.iter().enumerate() {
+                for (&p, steps) in t {
+                    expected
+                        .entry(p)
+                        .or_default()
+                        .push((w, *steps.first().unwrap()));
+                }
+            }
+            expected.retain(|&p, arrivals| p != Point(0, 0) && arrivals.len() >= min_wires);
+
+            let found = wires::crossings(&wires, min_wires);
+            let mut covered = BTreeSet::<Point>::new();
+            for i in &found.points {
+                assert_eq!(Some(&i.arrivals), expected.get(&i.point));
+                covered.insert(i.point);
+            }
+            for o in &found.overlaps {
+                for p in stretch(o.from, o.to) {
+                    assert!(o.wires.iter().all(|&w| traces[w].contains_key(&p)));
+                    covered.insert(p);
+                }
+            }
+            covered.remove(&Point(0, 0));
+            assert_eq!(covered, expected.keys().copied().collect());
+            // Meetings are listed as points only where no overlap has the same wires.
+            for (p, arrivals) in &expected {
+                let on = arrivals.iter().map(|a| a.0).collect::<Vec<usize>>();
+                let listed = found.points.iter().any(|i| i.point == *p);
+                let along = found
+                    .overlaps
+                    .iter()
+                    .any(|o| stretch(o.from, o.to).contains(p) && o.wires == on);
+                assert!(listed != along, "{p:?}");
+            }
+
+            let steps = |a: &Vec<(usize, i64)>| a.iter().map(|s| s.1).sum::<i64>();
+            let closest = expected.keys().map(|p| p.manhattan()).min();
+            assert_eq!(found.closest.map(|i| i.distance), closest);
+            let fewest = expected.values().map(steps).min();
+            assert_eq!(found.fewest_steps.map(|i| i.steps), fewest);
+        }
+    }
+}
+
+#[test]
+fn self_crossings_agree_with_tracing_every_point() {
+    let mut seed = 11;
+    for _ in 0..300 {
+        let w = random_wire(&mut seed);
+        let mut expected = trace(&w);
+        expected.retain(|_, visits| visits.len() > 1);
+
+        let found = wires::self_crossings(&w);
+        let mut covered = BTreeMap::<Point, usize>::new();
+        for r in &found.points {
+            assert_eq!(
+                r.visits,
+                expected[&r.point].iter().copied().collect::<Vec<i64>>()
+            );
+            covered.insert(r.point, r.visits.len());
+        }
+        for t in &found.retraces {
+            for p in stretch(t.from, t.to) {
+                covered.entry(p).or_insert(t.times);
+            }
+        }
+        let expected = expected
+            .into_iter()
+            .map(|(p, visits)| (p, visits.len()))
+            .collect::<BTreeMap<Point, usize>>();
+        assert_eq!(
+            covered,
+            expected,
+            "{:?}",
+            w.segments()
+                .iter()
+                .map(|s| (s.direction, s.length))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn renders_wires_and_marks_crossings() {
+    let (fst, snd) = (wire("R8,U5,L5,D3"), wire("U7,R6,D4,L4"));
+    let out = svg::render(&[&fst, &snd]);
+    assert!(out
+        .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-0.4 -7.4 8.8 7.8\">\n"));
+    assert!(out.ends_with("</svg>\n"));
+    // The y axis points down in SVG, so heights are flipped.
+    assert!(out
+        .contains("<polyline points=\"0,0 8,0 8,-5 3,-5 3,-2\" fill=\"none\" stroke=\"#1f77b4\""));
+    assert!(out
+        .contains("<polyline points=\"0,0 0,-7 6,-7 6,-3 2,-3\" fill=\"none\" stroke=\"#d62728\""));
+    assert!(out.contains("<title>origin</title>"));
+    // Only the closest crossing is highlighted.
+    assert_eq!(out.matches("fill=\"gold\"").count(), 1);
+    assert!(out.contains("<circle cx=\"3\" cy=\"-3\" r=\"0.2\" fill=\"gold\""));
+    assert!(out.contains("<title>d=6 s=40</title>"));
+    assert!(out.contains(">d=11 s=30</text>"));
+}
+
+#[test]
+fn renders_overlaps_as_stretches() {
+    let (fst, snd) = (wire("R10,U5"), wire("U1,R4,D1,R8"));
+    let out = svg::render(&[&fst, &snd]);
+    assert_eq!(out.matches("<line ").count(), 1);
+    assert!(out.contains(
+        "<line x1=\"4\" y1=\"0\" x2=\"10\" y2=\"0\" stroke=\"gold\" stroke-opacity=\"0.6\""
+    ));
+    assert!(out.contains("<title>overlap of wires 0, 1</title>"));
+    // The closest meeting lies along the overlap and is marked there.
+    assert_eq!(out.matches("<circle ").count(), 2);
+    assert!(out.contains("<circle cx=\"4\" cy=\"0\" r=\"0.3\" fill=\"gold\""));
+    assert!(out.contains("<title>d=4 s=10</title>"));
+}